@@ -0,0 +1,34 @@
+use poloto::build;
+use poloto::build::candle::Candle;
+use poloto_chrono::UnixTime;
+// PIPE me to a file!
+fn main() {
+    // Made up build times in minutes over one week.
+    let day = 24 * 60 * 60;
+    let start = 1_672_531_200;
+    let data = [
+        (12.0, 15.5, 10.5, 14.0),
+        (14.0, 16.0, 12.5, 13.0),
+        (13.0, 13.5, 9.0, 9.5),
+        (9.5, 12.0, 9.0, 11.5),
+        (11.5, 18.0, 11.0, 17.0),
+        (17.0, 17.5, 13.0, 14.5),
+        (14.5, 15.0, 12.0, 12.5),
+    ];
+
+    let candles = data
+        .iter()
+        .enumerate()
+        .map(|(i, &(open, high, low, close))| {
+            Candle::new(UnixTime(start + i as i64 * day), open, high, low, close)
+        });
+
+    poloto::frame_build()
+        .data(poloto::plots!(
+            build::plot("build time").candlestick(candles),
+            build::markers([], [0.0])
+        ))
+        .build_and_label(("Daily build times", "Date", "Minutes"))
+        .append_to(poloto::header().light_theme())
+        .render_stdout();
+}
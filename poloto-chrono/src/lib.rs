@@ -73,6 +73,7 @@ impl<T: TimeZone> UnixTimeTickFmt<T> {
     }
 }
 
+#[derive(Clone)]
 pub struct UnixTimeFmt<T: TimeZone + Display> {
     step: StepUnit,
     timezone: T,
@@ -285,4 +286,13 @@ impl PlotNum for UnixTime {
             [UnixTime(0), UnixTime(1)]
         }
     }
+
+    fn write_value(&self, w: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        let datetime = Utc.timestamp_opt(self.0, 0).unwrap();
+        write!(w, "{}", datetime.to_rfc3339_opts(SecondsFormat::Secs, true))
+    }
+
+    fn is_time() -> bool {
+        true
+    }
 }
@@ -43,6 +43,31 @@ fn test_leap_day() {
     assert_eq!(t, exp);
 }
 
+#[test]
+fn write_value() {
+    use poloto::plotnum::PlotNum;
+
+    let mut s = String::new();
+    UnixTime(1642121137).write_value(&mut s).unwrap();
+    assert_eq!(s, "2022-01-14T00:45:37Z");
+}
+
+#[test]
+fn vega_lite_temporal() {
+    let data = [(UnixTime(1642121137), 1.0), (UnixTime(1642207537), 2.0)];
+
+    let s = poloto::frame_build()
+        .data(poloto::build::plot("a").line(data))
+        .build_and_label(("title", "x", "y"))
+        .vega_lite()
+        .unwrap();
+
+    assert!(s.contains(r#"{"x":"2022-01-14T00:45:37Z","y":1.0}"#));
+    assert!(s.contains(r#""x":{"field":"x","type":"temporal""#));
+    assert!(s.contains(r#""y":{"field":"y","type":"quantitative""#));
+    assert!(!s.contains("UnixTime"));
+}
+
 /*
 #[test]
 fn test_hours() {
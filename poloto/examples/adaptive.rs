@@ -0,0 +1,26 @@
+use poloto::build;
+use poloto::build::function::{adaptive, parametric};
+// PIPE me to a file!
+fn main() {
+    // No need to pick a sample count or crop around the asymptotes.
+    let tan = adaptive(f64::tan, [-6.0, 6.0]).points();
+
+    // A rose curve.
+    let rose = parametric(
+        |t| {
+            let r = 3.0 * (4.0 * t).cos();
+            (r * t.cos(), r * t.sin())
+        },
+        [0.0, std::f64::consts::TAU],
+    )
+    .points();
+
+    poloto::frame_build()
+        .data(poloto::plots!(
+            build::plot("tan(x)").line(tan),
+            build::plot("rose").line(rose)
+        ))
+        .build_and_label(("Adaptive sampling", "x", "y"))
+        .append_to(poloto::header().light_theme())
+        .render_stdout();
+}
@@ -0,0 +1,20 @@
+use poloto::build;
+// PIPE me to a file!
+fn main() {
+    let data = (0..100).map(|i| {
+        let x = i as f64 / 10.0;
+        (x, x.sin() * (-x / 5.0).exp())
+    });
+
+    poloto::frame_build()
+        .data(poloto::plots!(
+            build::plot("damped").line(data),
+            build::annotate("first peak", 1.37, 0.74)
+                .with_offset([60.0, 20.0])
+                .with_arrow(),
+            build::annotate("settles", 9.0, 0.0).with_offset([0.0, -30.0])
+        ))
+        .build_and_label(("Damped sine", "x", "y"))
+        .append_to(poloto::header().light_theme())
+        .render_stdout();
+}
@@ -0,0 +1,30 @@
+use poloto::build;
+use poloto::build::bubble::{Bubble, Bubbles};
+// PIPE me to a file!
+fn main() {
+    // Made up population, life expectancy and income of some countries.
+    let data = [
+        (1_200.0, 62.0, 35.0, 1.0),
+        (4_500.0, 70.0, 120.0, 2.0),
+        (9_800.0, 74.0, 60.0, 3.0),
+        (16_000.0, 77.0, 210.0, 1.0),
+        (32_000.0, 80.0, 50.0, 2.0),
+        (48_000.0, 82.0, 10.0, 3.0),
+    ];
+
+    let bubbles = Bubbles::new(
+        data.iter()
+            .map(|&(income, life, pop, region)| Bubble::new(income, life, pop).with_color(region)),
+    )
+    .with_max_radius(30.0)
+    .with_colormap(poloto::colormap::Colormap::viridis());
+
+    poloto::frame_build()
+        .data(poloto::plots!(
+            build::plot("population (M)").bubble(bubbles),
+            build::markers([0.0, 55_000.0], [55.0, 90.0])
+        ))
+        .build_and_label(("countries", "income", "life expectancy"))
+        .append_to(poloto::header().light_theme())
+        .render_stdout();
+}
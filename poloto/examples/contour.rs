@@ -0,0 +1,21 @@
+use poloto::build;
+use poloto::build::contour::Contour;
+// PIPE me to a file!
+fn main() {
+    let n = 60;
+    let coord = |k: usize| -3.0 + 6.0 * k as f64 / (n - 1) as f64;
+
+    let f = |x: f64, y: f64| (x * x + y - 11.0).powi(2) / 100.0 + (x + y * y - 7.0).powi(2) / 100.0;
+
+    let rows = (0..n).map(|j| (0..n).map(move |i| f(coord(i), coord(j))));
+
+    let contour = Contour::new([-3.0, 3.0], [-3.0, 3.0], rows).with_num_levels(6);
+
+    let minimum = build::plot("minimum").scatter([[3.0, 2.0]]);
+
+    poloto::frame_build()
+        .data(poloto::plots!(contour.build(), minimum))
+        .build_and_label(("himmelblau", "x", "y"))
+        .append_to(poloto::header().light_theme())
+        .render_stdout();
+}
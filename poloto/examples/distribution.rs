@@ -0,0 +1,24 @@
+use poloto::build;
+use poloto::build::distribution::Kde;
+use poloto::build::histogram::{Bins, Histogram};
+// PIPE me to a file!
+fn main() {
+    // Latencies with a long tail.
+    let samples: Vec<f64> = (1..500)
+        .map(|i| {
+            let u = (i as f64 * 0.618_033_988_7).fract();
+            20.0 - 15.0 * (1.0 - u).ln()
+        })
+        .collect();
+
+    let h = Histogram::new(samples.iter().copied(), Bins::FreedmanDiaconis).density();
+
+    poloto::frame_build()
+        .data(poloto::plots!(
+            build::plot("samples").histogram(h.points()),
+            build::plot("kde").line(Kde::new(samples).points())
+        ))
+        .build_and_label(("Latency density", "ms", "density"))
+        .append_to(poloto::header().light_theme())
+        .render_stdout();
+}
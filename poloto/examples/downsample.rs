@@ -0,0 +1,18 @@
+use poloto::build;
+use poloto::render::downsample::Downsample;
+// PIPE me to a file!
+fn main() {
+    // A million points, but the svg only gets about one per pixel column.
+    let data = (0..1_000_000).map(|i| {
+        let x = i as f64 / 1000.0;
+        (x, x.sin() + (x * 37.0).sin() * 0.1)
+    });
+
+    poloto::frame()
+        .with_downsample(Downsample::Lttb)
+        .build()
+        .data(build::plot("signal").line(data))
+        .build_and_label(("Downsampled", "x", "y"))
+        .append_to(poloto::header().light_theme())
+        .render_stdout();
+}
@@ -0,0 +1,25 @@
+use poloto::build;
+use poloto::build::fit::{Fit, Model};
+// PIPE me to a file!
+fn main() {
+    // Running time of some benchmark for different input sizes.
+    let timings = [
+        (100.0, 0.9),
+        (200.0, 3.8),
+        (400.0, 15.5),
+        (800.0, 66.0),
+        (1600.0, 250.0),
+        (3200.0, 1040.0),
+    ];
+
+    let fit = Fit::new(Model::Power, timings).unwrap();
+
+    poloto::frame_build()
+        .data(poloto::plots!(
+            build::plot("timings").scatter(timings),
+            build::plot(&fit).line(fit.points(100))
+        ))
+        .build_and_label(("Scaling", "n", "ms"))
+        .append_to(poloto::header().light_theme())
+        .render_stdout();
+}
@@ -0,0 +1,32 @@
+use poloto::build;
+use poloto::build::heatmap::Heatmap;
+// PIPE me to a file!
+fn main() {
+    let days = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+
+    // Made up latencies by hour of day and weekday.
+    let rows = (0..days.len()).map(|d| {
+        (0..24).map(move |h| {
+            let busy = (-((h as f64 - 14.0) / 4.0).powi(2)).exp();
+            let weekend = if d >= 5 { 0.4 } else { 1.0 };
+            20.0 + 80.0 * busy * weekend
+        })
+    });
+
+    let heatmap = Heatmap::new(
+        (0..=24).map(|x| x as f64),
+        (0..=days.len()).map(|x| x as f64),
+        rows,
+    )
+    .with_colormap(poloto::colormap::Colormap::magma());
+
+    poloto::frame_build()
+        .data(build::plot("latency (ms)").heatmap(heatmap))
+        .map_yticks(|_| {
+            poloto::ticks::from_iter((0..days.len()).map(|x| x as f64 + 0.5))
+                .with_tick_fmt(move |&x| days[x as usize])
+        })
+        .build_and_label(("weekly latency", "hour", "day"))
+        .append_to(poloto::header().light_theme())
+        .render_stdout();
+}
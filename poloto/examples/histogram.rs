@@ -0,0 +1,24 @@
+use poloto::build;
+use poloto::build::histogram::{Bins, Histogram};
+// PIPE me to a file!
+fn main() {
+    // Roughly normal samples from the sum of a few evenly spread values.
+    let samples: Vec<f64> = (0..1000)
+        .map(|i| {
+            (1..=4)
+                .map(|k| ((i * k) as f64 * 0.618_033_988_7).fract())
+                .sum::<f64>()
+        })
+        .collect();
+
+    let h = Histogram::new(samples, Bins::FreedmanDiaconis).density();
+
+    poloto::frame_build()
+        .data(poloto::plots!(
+            build::plot("samples").histogram(h.points()),
+            build::markers([], [0.0])
+        ))
+        .build_and_label(("Binned samples", "value", "density"))
+        .append_to(poloto::header().light_theme())
+        .render_stdout();
+}
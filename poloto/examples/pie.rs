@@ -0,0 +1,16 @@
+use poloto::build::pie::Pie;
+// PIPE me to a file!
+fn main() {
+    let data = [
+        ("potato", 20.0),
+        ("broccoli", 14.0),
+        ("pizza", 53.0),
+        ("avocado", 30.0),
+    ];
+
+    poloto::frame_build()
+        .pie(Pie::new(data).donut(0.5).with_percentages())
+        .label(("Favorite Foods", "", ""))
+        .append_to(poloto::header().light_theme())
+        .render_stdout();
+}
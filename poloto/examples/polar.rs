@@ -0,0 +1,17 @@
+use poloto::build;
+// PIPE me to a file!
+fn main() {
+    // Gain of a made up antenna in dB.
+    let pattern = (0..=360).map(|d| {
+        let t = (d as f64).to_radians();
+        (d as f64, 20.0 * (1.0 + t.cos()).max(0.4).log10() + 10.0)
+    });
+
+    poloto::frame_build()
+        .polar(build::plot("gain").line(pattern))
+        .with_degrees()
+        .clockwise_from_top()
+        .label(("Antenna Pattern", "", ""))
+        .append_to(poloto::header().light_theme())
+        .render_stdout();
+}
@@ -0,0 +1,23 @@
+use poloto::build;
+use poloto::build::quiver::Quiver;
+// PIPE me to a file!
+fn main() {
+    // A vortex around the origin.
+    let field = (-5..=5).flat_map(|i| {
+        (-5..=5).map(move |j| {
+            let (x, y) = (i as f64, j as f64);
+            (x, y, -y, x)
+        })
+    });
+
+    poloto::frame()
+        .preserve_aspect()
+        .build()
+        .data(
+            build::plot("velocity")
+                .quiver(Quiver::new(field).with_colormap(poloto::colormap::Colormap::magma())),
+        )
+        .build_and_label(("Vortex", "x", "y"))
+        .append_to(poloto::header().light_theme())
+        .render_stdout();
+}
@@ -0,0 +1,16 @@
+use poloto::build;
+use poloto::build::polar::Radar;
+// PIPE me to a file!
+fn main() {
+    let radar = Radar::new(["speed", "power", "range", "armor", "cost"]);
+
+    poloto::frame_build()
+        .polar(poloto::plots!(
+            build::plot("car").line_fill(radar.points([4.0, 3.0, 2.0, 5.0, 3.0])),
+            build::plot("bike").line(radar.points([5.0, 1.0, 3.0, 1.0, 1.0]))
+        ))
+        .with_radar(&radar)
+        .label(("Vehicles", "", ""))
+        .append_to(poloto::header().light_theme())
+        .render_stdout();
+}
@@ -0,0 +1,19 @@
+use poloto::build;
+// PIPE me to a file!
+fn main() {
+    let latency = [
+        120.0, 135.0, 128.0, 142.0, 180.0, 260.0, 310.0, 240.0, 150.0, 130.0, 125.0, 132.0,
+    ];
+
+    poloto::frame_build()
+        .data(poloto::plots!(
+            build::vspan(4.0, 7.0).with_label("incident"),
+            build::plot("p99").line(latency.iter().enumerate().map(|(i, v)| (i as f64, *v))),
+            build::hline(200.0).with_label("SLO"),
+            build::vline(9.0).with_label("deploy"),
+            build::markers([], [0.0])
+        ))
+        .build_and_label(("Request latency", "hour", "ms"))
+        .append_to(poloto::header().light_theme())
+        .render_stdout();
+}
@@ -0,0 +1,29 @@
+use poloto::build;
+use poloto::prelude::*;
+// PIPE me to a file!
+fn main() {
+    // A noisy signal with a dropout in the middle.
+    let data: Vec<(f64, f64)> = (0..300)
+        .map(|i| {
+            let x = i as f64 / 10.0;
+            let noise = ((i as f64 * 0.618_033_988_7).fract() - 0.5) * 2.0;
+            let y = if (140..160).contains(&i) {
+                f64::NAN
+            } else {
+                x.sin() * 3.0 + noise
+            };
+            (x, y)
+        })
+        .collect();
+
+    poloto::frame_build()
+        .data(poloto::plots!(
+            build::plot("raw").scatter(data.iter().copied()),
+            build::plot("moving average").line(data.iter().copied().moving_average(15)),
+            build::plot("ema").line(data.iter().copied().exponential_moving_average(0.1)),
+            build::plot("rolling max").line(data.iter().copied().rolling_max(15))
+        ))
+        .build_and_label(("Smoothing", "t", "y"))
+        .append_to(poloto::header().light_theme())
+        .render_stdout();
+}
@@ -0,0 +1,27 @@
+use poloto::build;
+// PIPE me to a file!
+fn main() {
+    // Ten million points are generated twice but never held in memory at once.
+    let n = 10_000_000;
+    let source = || {
+        (0..n).map(move |i| {
+            let x = i as f64 / n as f64 * 20.0;
+            (x, x.sin() * (-x / 10.0).exp())
+        })
+    };
+
+    poloto::frame()
+        .with_downsample(poloto::render::downsample::Downsample::MinMax)
+        .build()
+        .data(poloto::plots!(
+            build::plot("streamed").line(build::streamed(source)),
+            build::plot("bounded").line(build::bounded(
+                source().map(|(x, y)| (x, y / 2.0)),
+                [0.0, 20.0],
+                [-1.0, 1.0]
+            ))
+        ))
+        .build_and_label(("Streaming", "x", "y"))
+        .append_to(poloto::header().light_theme())
+        .render_stdout();
+}
@@ -0,0 +1,90 @@
+//!
+//! Write text at data coordinates, optionally with an arrow pointing at the point.
+//!
+use super::*;
+
+///
+/// Where to put the text of an annotation relative to its point.
+///
+#[derive(Copy, Clone, Debug)]
+pub struct AnnotationStyle {
+    pub offset: [f64; 2],
+    pub arrow: bool,
+}
+
+///
+/// Text anchored to a point. Created by [`annotate`].
+///
+/// Annotations don't show up in the legend and don't use up a plot color.
+///
+#[derive(Copy, Clone, Debug)]
+pub struct Annotation<D, X, Y> {
+    text: D,
+    x: X,
+    y: Y,
+    style: AnnotationStyle,
+    grow: bool,
+}
+
+///
+/// Write some text at the point `(x, y)`.
+///
+pub fn annotate<D: Display, X: PlotNum, Y: PlotNum>(text: D, x: X, y: Y) -> Annotation<D, X, Y> {
+    Annotation {
+        text,
+        x,
+        y,
+        style: AnnotationStyle {
+            offset: [0.0, 0.0],
+            arrow: false,
+        },
+        grow: true,
+    }
+}
+
+impl<D: Display, X: PlotNum, Y: PlotNum> Annotation<D, X, Y> {
+    ///
+    /// Move the text this many pixels away from the point. Positive y moves it up.
+    ///
+    pub fn with_offset(mut self, offset: [f64; 2]) -> Self {
+        self.style.offset = offset;
+        self
+    }
+
+    ///
+    /// Draw an arrow from the text to the point. There needs to be an offset for it to show.
+    ///
+    pub fn with_arrow(mut self) -> Self {
+        self.style.arrow = true;
+        self
+    }
+
+    ///
+    /// Whether the point should be kept in view like the points of a plot. The default is true.
+    ///
+    pub fn grow_area(mut self, grow: bool) -> Self {
+        self.grow = grow;
+        self
+    }
+}
+
+impl<D: Display, X: PlotNum, Y: PlotNum> PlotIterator for Annotation<D, X, Y> {
+    type L = (X, Y);
+    type P = PlotIterCreator<std::iter::Once<(X, Y)>, D>;
+    type D = D;
+    fn unpack(self) -> PlotRes<Self::P, Self::L> {
+        let mut area = Area::new();
+        if self.grow {
+            area.grow(Some(&self.x), Some(&self.y));
+        }
+        PlotRes {
+            area,
+            it: PlotIterCreator::new(
+                self.text,
+                PlotMetaType::Annotation(self.style),
+                std::iter::once((self.x, self.y)),
+            ),
+            num_plots: 1,
+        }
+    }
+}
@@ -0,0 +1,145 @@
+//!
+//! Create bubble charts where each point has its own size and optionally its own color.
+//!
+use super::*;
+use crate::colormap::Colormap;
+
+///
+/// Sizing and coloring of a bubble chart, used when rendering the circles and the legend.
+///
+#[derive(Copy, Clone, Debug)]
+pub struct BubbleStyle {
+    pub max_size: f64,
+    pub max_radius: f64,
+    pub colormap: Option<Colormap>,
+    pub color_range: [f64; 2],
+}
+
+impl BubbleStyle {
+    ///
+    /// The radius of a circle in pixels, such that its area is proportional to its size.
+    ///
+    pub fn radius(&self, size: f64) -> f64 {
+        if self.max_size > 0.0 {
+            self.max_radius * (size.max(0.0) / self.max_size).sqrt()
+        } else {
+            0.0
+        }
+    }
+}
+
+///
+/// A point with a size and an optional color value.
+///
+#[derive(Copy, Clone, Debug)]
+pub struct Bubble<X, Y> {
+    pub x: X,
+    pub y: Y,
+    pub size: f64,
+    pub color: Option<f64>,
+}
+
+impl<X, Y> Bubble<X, Y> {
+    pub fn new(x: X, y: Y, size: f64) -> Self {
+        Bubble {
+            x,
+            y,
+            size,
+            color: None,
+        }
+    }
+    pub fn with_color(mut self, color: f64) -> Self {
+        self.color = Some(color);
+        self
+    }
+}
+
+impl<X: PlotNum, Y: PlotNum> Point for Bubble<X, Y> {
+    type X = X;
+    type Y = Y;
+    fn get(&self) -> (&X, &Y) {
+        (&self.x, &self.y)
+    }
+    fn value(&self) -> Option<f64> {
+        Some(self.size)
+    }
+    fn color(&self) -> Option<f64> {
+        self.color
+    }
+}
+
+impl<X: PlotNum, Y: PlotNum> Unwrapper for Bubble<X, Y> {
+    type Item = Bubble<X, Y>;
+    fn unwrap(self) -> Self {
+        self
+    }
+}
+
+///
+/// A list of bubbles along with how to size and color them.
+///
+#[derive(Clone, Debug)]
+pub struct Bubbles<X, Y> {
+    points: Vec<Bubble<X, Y>>,
+    max_radius: f64,
+    colormap: Option<Colormap>,
+}
+
+impl<X: PlotNum, Y: PlotNum> Bubbles<X, Y> {
+    pub fn new(points: impl IntoIterator<Item = Bubble<X, Y>>) -> Self {
+        Bubbles {
+            points: points.into_iter().collect(),
+            max_radius: 20.0,
+            colormap: None,
+        }
+    }
+
+    ///
+    /// The radius in pixels of the largest bubble. The default is 20.
+    ///
+    pub fn with_max_radius(mut self, radius: f64) -> Self {
+        self.max_radius = radius;
+        self
+    }
+
+    ///
+    /// Color each bubble by its color value instead of the color of the plot.
+    ///
+    pub fn with_colormap(mut self, colormap: Colormap) -> Self {
+        self.colormap = Some(colormap);
+        self
+    }
+
+    pub(crate) fn style(&self) -> BubbleStyle {
+        let max_size = self
+            .points
+            .iter()
+            .map(|b| b.size)
+            .filter(|s| s.is_finite())
+            .fold(0.0, f64::max);
+
+        let mut colors = self
+            .points
+            .iter()
+            .filter_map(|b| b.color)
+            .filter(|c| c.is_finite());
+        let first = colors.next().unwrap_or(0.0);
+        let [min, max] = colors.fold([first, first], |[min, max], c| [min.min(c), max.max(c)]);
+        let color_range = if min == max {
+            f64::unit_range(Some(min))
+        } else {
+            [min, max]
+        };
+
+        BubbleStyle {
+            max_size,
+            max_radius: self.max_radius,
+            colormap: self.colormap,
+            color_range,
+        }
+    }
+
+    pub(crate) fn into_points(self) -> Vec<Bubble<X, Y>> {
+        self.points
+    }
+}
@@ -0,0 +1,42 @@
+//!
+//! Create candlestick charts from open, high, low and close values.
+//!
+use super::*;
+
+///
+/// The open, high, low and close values of one bucket.
+///
+#[derive(Copy, Clone, Debug)]
+pub struct Candle<X, Y> {
+    pub x: X,
+    pub open: Y,
+    pub high: Y,
+    pub low: Y,
+    pub close: Y,
+}
+
+impl<X: PlotNum, Y: PlotNum> Candle<X, Y> {
+    pub fn new(x: X, open: Y, high: Y, low: Y, close: Y) -> Self {
+        Candle {
+            x,
+            open,
+            high,
+            low,
+            close,
+        }
+    }
+
+    ///
+    /// The four points of a candle in the order open, high, low, close.
+    ///
+    pub(crate) fn points(self) -> [(X, Y); 4] {
+        let Candle {
+            x,
+            open,
+            high,
+            low,
+            close,
+        } = self;
+        [(x, open), (x, high), (x, low), (x, close)]
+    }
+}
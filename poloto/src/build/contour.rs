@@ -0,0 +1,278 @@
+//!
+//! Create contour lines from a regular grid of values using marching squares.
+//!
+use super::*;
+use std::collections::HashMap;
+
+///
+/// A regular grid of values along with the levels to draw contour lines at.
+///
+#[derive(Clone, Debug)]
+pub struct Contour {
+    xrange: [f64; 2],
+    yrange: [f64; 2],
+    cols: usize,
+    rows: usize,
+    values: Vec<f64>,
+    levels: Option<Vec<f64>>,
+    num_levels: usize,
+}
+
+///
+/// An edge of the grid between two neighbouring samples.
+/// Horizontal edges go from `(i,j)` to `(i+1,j)`, vertical ones from `(i,j)` to `(i,j+1)`.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+enum Edge {
+    Horizontal(usize, usize),
+    Vertical(usize, usize),
+}
+
+impl Contour {
+    ///
+    /// Create a contour from rows of samples. The samples are spread evenly
+    /// over the x range within a row, and the rows are spread evenly over the y range.
+    ///
+    /// Cells with non finite samples are skipped.
+    ///
+    pub fn new<R: IntoIterator<Item = f64>>(
+        xrange: [f64; 2],
+        yrange: [f64; 2],
+        rows: impl IntoIterator<Item = R>,
+    ) -> Self {
+        let mut cols = None;
+        let mut num_rows = 0;
+        let mut values = vec![];
+        for row in rows {
+            let len = values.len();
+            values.extend(row);
+            let c = values.len() - len;
+            assert_eq!(*cols.get_or_insert(c), c, "rows must be the same length");
+            num_rows += 1;
+        }
+        let cols = cols.unwrap_or(0);
+        assert!(
+            cols >= 2 && num_rows >= 2,
+            "need at least a 2x2 grid of samples"
+        );
+
+        Contour {
+            xrange,
+            yrange,
+            cols,
+            rows: num_rows,
+            values,
+            levels: None,
+            num_levels: 8,
+        }
+    }
+
+    ///
+    /// Draw contour lines at these levels.
+    ///
+    pub fn with_levels(mut self, levels: impl IntoIterator<Item = f64>) -> Self {
+        self.levels = Some(levels.into_iter().collect());
+        self
+    }
+
+    ///
+    /// Pick around this many evenly spaced levels between the min and max sample.
+    /// The default is 8.
+    ///
+    pub fn with_num_levels(mut self, num: usize) -> Self {
+        self.levels = None;
+        self.num_levels = num;
+        self
+    }
+
+    ///
+    /// The levels that contour lines will be drawn at, along with the step between them
+    /// if they were picked automatically.
+    ///
+    fn levels_and_step(&self) -> (Vec<f64>, Option<f64>) {
+        if let Some(levels) = &self.levels {
+            return (levels.clone(), None);
+        }
+
+        let mut it = self.values.iter().copied().filter(|x| x.is_finite());
+        let Some(first) = it.next() else {
+            return (vec![], None);
+        };
+        let [min, max] = it.fold([first, first], |[min, max], x| [min.min(x), max.max(x)]);
+        if min == max || self.num_levels == 0 {
+            return (vec![], None);
+        }
+
+        //Pick a step of the form 1, 2 or 5 times a power of ten.
+        let rough = (max - min) / (self.num_levels + 1) as f64;
+        let mag = 10f64.powf(rough.log10().floor());
+        let step = [1.0, 2.0, 5.0, 10.0]
+            .iter()
+            .map(|m| m * mag)
+            .find(|&s| s >= rough)
+            .unwrap_or(10.0 * mag);
+
+        let start = (min / step).floor() as i64 + 1;
+        let levels = (start..)
+            .map(|k| k as f64 * step)
+            .take_while(|&l| l < max)
+            .collect();
+        (levels, Some(step))
+    }
+
+    pub fn levels(&self) -> Vec<f64> {
+        self.levels_and_step().0
+    }
+
+    fn get(&self, i: usize, j: usize) -> f64 {
+        self.values[j * self.cols + i]
+    }
+
+    fn edge_point(&self, edge: Edge, level: f64) -> (f64, f64) {
+        let ((i1, j1), (i2, j2)) = match edge {
+            Edge::Horizontal(i, j) => ((i, j), (i + 1, j)),
+            Edge::Vertical(i, j) => ((i, j), (i, j + 1)),
+        };
+        let (a, b) = (self.get(i1, j1), self.get(i2, j2));
+        let t = (level - a) / (b - a);
+
+        let [x0, x1] = self.xrange;
+        let [y0, y1] = self.yrange;
+        let dx = (x1 - x0) / (self.cols - 1) as f64;
+        let dy = (y1 - y0) / (self.rows - 1) as f64;
+
+        (
+            x0 + (i1 as f64 + t * (i2 - i1) as f64) * dx,
+            y0 + (j1 as f64 + t * (j2 - j1) as f64) * dy,
+        )
+    }
+
+    ///
+    /// Find the segments of one level, each connecting two grid edges.
+    ///
+    fn segments(&self, level: f64) -> Vec<[Edge; 2]> {
+        use Edge::*;
+        let mut segs = vec![];
+        for j in 0..self.rows - 1 {
+            for i in 0..self.cols - 1 {
+                let corners = [
+                    self.get(i, j),
+                    self.get(i + 1, j),
+                    self.get(i + 1, j + 1),
+                    self.get(i, j + 1),
+                ];
+                if !corners.iter().all(|x| x.is_finite()) {
+                    continue;
+                }
+
+                let case = corners
+                    .iter()
+                    .enumerate()
+                    .fold(0, |acc, (k, &v)| acc | (((v >= level) as u8) << k));
+
+                let bottom = Horizontal(i, j);
+                let right = Vertical(i + 1, j);
+                let top = Horizontal(i, j + 1);
+                let left = Vertical(i, j);
+
+                //Use the average of the corners to resolve saddles.
+                let center_above = corners.iter().sum::<f64>() / 4.0 >= level;
+
+                match case {
+                    0 | 15 => {}
+                    1 | 14 => segs.push([left, bottom]),
+                    2 | 13 => segs.push([bottom, right]),
+                    3 | 12 => segs.push([left, right]),
+                    4 | 11 => segs.push([right, top]),
+                    6 | 9 => segs.push([bottom, top]),
+                    7 | 8 => segs.push([left, top]),
+                    5 => {
+                        if center_above {
+                            segs.push([left, top]);
+                            segs.push([bottom, right]);
+                        } else {
+                            segs.push([left, bottom]);
+                            segs.push([right, top]);
+                        }
+                    }
+                    10 => {
+                        if center_above {
+                            segs.push([left, bottom]);
+                            segs.push([right, top]);
+                        } else {
+                            segs.push([left, top]);
+                            segs.push([bottom, right]);
+                        }
+                    }
+                    _ => unreachable!(),
+                }
+            }
+        }
+        segs
+    }
+
+    ///
+    /// Compute the contour lines of one level. Separate lines are delimited by a point
+    /// made up of holes, so they can be drawn by a single line plot.
+    ///
+    pub fn lines(&self, level: f64) -> Vec<(f64, f64)> {
+        let segs = self.segments(level);
+
+        let mut adj: HashMap<Edge, Vec<usize>> = HashMap::new();
+        for (k, [a, b]) in segs.iter().enumerate() {
+            adj.entry(*a).or_default().push(k);
+            adj.entry(*b).or_default().push(k);
+        }
+
+        let mut used = vec![false; segs.len()];
+
+        //Follow unused segments starting from an edge.
+        let walk = |start: Edge, used: &mut Vec<bool>| {
+            let mut out = vec![];
+            let mut curr = start;
+            while let Some(&k) = adj[&curr].iter().find(|&&k| !used[k]) {
+                used[k] = true;
+                let [a, b] = segs[k];
+                curr = if a == curr { b } else { a };
+                out.push(curr);
+            }
+            out
+        };
+
+        let mut points = vec![];
+        for k in 0..segs.len() {
+            if used[k] {
+                continue;
+            }
+            used[k] = true;
+            let [a, b] = segs[k];
+
+            let mut line = walk(a, &mut used);
+            line.reverse();
+            line.push(a);
+            line.push(b);
+            line.extend(walk(b, &mut used));
+
+            if !points.is_empty() {
+                points.push((f64::hole(), f64::hole()));
+            }
+            points.extend(line.into_iter().map(|e| self.edge_point(e, level)));
+        }
+        points
+    }
+
+    ///
+    /// Create one line plot per level, named after the level.
+    ///
+    pub fn build(self) -> impl PlotIterator<L = (f64, f64)> + Clone {
+        let (levels, step) = self.levels_and_step();
+        levels
+            .into_iter()
+            .map(|level| {
+                let mut name = String::new();
+                util::write_interval_float(&mut name, level, step).unwrap();
+                plot(name).line(self.lines(level))
+            })
+            .collect::<Vec<_>>()
+    }
+}
@@ -0,0 +1,178 @@
+//!
+//! Estimate the distribution of raw samples as curves that can be passed to [`SinglePlotBuilder::line`].
+//!
+use super::*;
+use std::f64::consts::PI;
+
+fn sorted_samples(samples: impl IntoIterator<Item = f64>) -> Vec<f64> {
+    let mut samples: Vec<f64> = samples.into_iter().filter(|v| v.is_finite()).collect();
+    samples.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    samples
+}
+
+///
+/// Empirical cumulative distribution function of some samples.
+///
+#[derive(Clone, Debug)]
+pub struct Ecdf {
+    samples: Vec<f64>,
+}
+
+impl Ecdf {
+    ///
+    /// Samples that are NaN or infinite are skipped.
+    ///
+    pub fn new(samples: impl IntoIterator<Item = f64>) -> Self {
+        Ecdf {
+            samples: sorted_samples(samples),
+        }
+    }
+
+    ///
+    /// The fraction of samples less than or equal to `x`.
+    ///
+    pub fn eval(&self, x: f64) -> f64 {
+        if self.samples.is_empty() {
+            return 0.0;
+        }
+        self.samples.partition_point(|&v| v <= x) as f64 / self.samples.len() as f64
+    }
+
+    ///
+    /// The corners of the step curve, going up at every distinct sample and ending at one.
+    ///
+    pub fn points(&self) -> Vec<(f64, f64)> {
+        let n = self.samples.len() as f64;
+        let mut points = vec![];
+        let mut last = 0.0;
+        let mut i = 0;
+        while i < self.samples.len() {
+            let v = self.samples[i];
+            i += self.samples[i..].partition_point(|&k| k == v);
+            let p = i as f64 / n;
+            points.push((v, last));
+            points.push((v, p));
+            last = p;
+        }
+        points
+    }
+}
+
+///
+/// Gaussian kernel density estimate of some samples.
+///
+#[derive(Clone, Debug)]
+pub struct Kde {
+    samples: Vec<f64>,
+    bandwidth: Option<f64>,
+    num_points: usize,
+    range: Option<[f64; 2]>,
+}
+
+impl Kde {
+    ///
+    /// Samples that are NaN or infinite are skipped.
+    ///
+    pub fn new(samples: impl IntoIterator<Item = f64>) -> Self {
+        Kde {
+            samples: sorted_samples(samples),
+            bandwidth: None,
+            num_points: 200,
+            range: None,
+        }
+    }
+
+    ///
+    /// Use this bandwidth instead of Silverman's rule of thumb.
+    ///
+    pub fn with_bandwidth(mut self, bandwidth: f64) -> Self {
+        assert!(bandwidth > 0.0, "bandwidth must be positive");
+        self.bandwidth = Some(bandwidth);
+        self
+    }
+
+    ///
+    /// How many points the curve is made of. The default is 200.
+    ///
+    pub fn with_num_points(mut self, num_points: usize) -> Self {
+        assert!(num_points >= 2, "need at least two points");
+        self.num_points = num_points;
+        self
+    }
+
+    ///
+    /// Evaluate the curve over this range. By default it goes three bandwidths past the smallest and largest sample.
+    ///
+    pub fn with_range(mut self, range: [f64; 2]) -> Self {
+        self.range = Some(range);
+        self
+    }
+
+    ///
+    /// The bandwidth in use. Unless set, this is `0.9 * min(sd, IQR / 1.34) * n^(-1/5)`.
+    ///
+    pub fn bandwidth(&self) -> f64 {
+        if let Some(h) = self.bandwidth {
+            return h;
+        }
+
+        let n = self.samples.len() as f64;
+        if self.samples.len() < 2 {
+            return 1.0;
+        }
+        let mean = self.samples.iter().sum::<f64>() / n;
+        let sd = (self.samples.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt();
+        let iqr =
+            histogram::quantile(&self.samples, 0.75) - histogram::quantile(&self.samples, 0.25);
+
+        let spread = match iqr / 1.34 {
+            s if s > 0.0 => sd.min(s),
+            _ => sd,
+        };
+
+        if spread > 0.0 {
+            0.9 * spread * n.powf(-0.2)
+        } else {
+            1.0
+        }
+    }
+
+    ///
+    /// The estimated density at `x`.
+    ///
+    pub fn eval(&self, x: f64) -> f64 {
+        self.eval_with(x, self.bandwidth())
+    }
+
+    fn eval_with(&self, x: f64, h: f64) -> f64 {
+        if self.samples.is_empty() {
+            return 0.0;
+        }
+        let norm = 1.0 / (self.samples.len() as f64 * h * (2.0 * PI).sqrt());
+        self.samples
+            .iter()
+            .map(|v| (-0.5 * ((x - v) / h).powi(2)).exp())
+            .sum::<f64>()
+            * norm
+    }
+
+    ///
+    /// Evenly spaced points along the curve.
+    ///
+    pub fn points(&self) -> Vec<(f64, f64)> {
+        let h = self.bandwidth();
+        let [a, b] = match (self.range, self.samples.first(), self.samples.last()) {
+            (Some(r), _, _) => r,
+            (None, Some(&min), Some(&max)) => [min - 3.0 * h, max + 3.0 * h],
+            _ => return vec![],
+        };
+
+        let step = (b - a) / (self.num_points - 1) as f64;
+        (0..self.num_points)
+            .map(|i| {
+                let x = a + i as f64 * step;
+                (x, self.eval_with(x, h))
+            })
+            .collect()
+    }
+}
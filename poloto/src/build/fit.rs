@@ -0,0 +1,292 @@
+//!
+//! Fit models to a series to draw trend lines.
+//!
+//! The [`Display`] impl of [`Fit`] writes the equation and R², so it can be used as the name of a plot.
+//!
+use std::fmt;
+
+///
+/// The kind of curve to fit.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Model {
+    /// `y = c0 + c1 * x`
+    Linear,
+    /// `y = c0 + c1 * x + c2 * x^2 + ...` with this degree.
+    Polynomial(usize),
+    /// `y = a * e^(b * x)`. Points with `y <= 0` are ignored.
+    Exponential,
+    /// `y = a * x^b`. Points with `x <= 0` or `y <= 0` are ignored.
+    Power,
+}
+
+///
+/// A model fit to some points with least squares.
+///
+#[derive(Clone, Debug)]
+pub struct Fit {
+    model: Model,
+    poly: Scaled,
+    coeffs: Vec<f64>,
+    r_squared: f64,
+    range: [f64; 2],
+}
+
+impl Fit {
+    ///
+    /// Fit the model to the points. Non finite points are ignored.
+    ///
+    /// Returns `None` if there are not enough usable points to pin down the model.
+    ///
+    pub fn new(model: Model, points: impl IntoIterator<Item = (f64, f64)>) -> Option<Self> {
+        let points: Vec<_> = points
+            .into_iter()
+            .filter(|(x, y)| x.is_finite() && y.is_finite())
+            .filter(|&(x, y)| match model {
+                Model::Linear | Model::Polynomial(_) => true,
+                Model::Exponential => y > 0.0,
+                Model::Power => x > 0.0 && y > 0.0,
+            })
+            .collect();
+
+        //Exponential and power models are linear after taking logs.
+        let poly = match model {
+            Model::Linear => polyfit(points.iter().copied(), 1)?,
+            Model::Polynomial(degree) => polyfit(points.iter().copied(), degree)?,
+            Model::Exponential => polyfit(points.iter().map(|&(x, y)| (x, y.ln())), 1)?,
+            Model::Power => polyfit(points.iter().map(|&(x, y)| (x.ln(), y.ln())), 1)?,
+        };
+
+        let coeffs = match (model, poly.expand()) {
+            (Model::Linear | Model::Polynomial(_), c) => c,
+            (Model::Exponential | Model::Power, c) => vec![c[0].exp(), c[1]],
+        };
+
+        let range = points
+            .iter()
+            .fold([f64::INFINITY, f64::NEG_INFINITY], |[a, b], &(x, _)| {
+                [a.min(x), b.max(x)]
+            });
+
+        let mut fit = Fit {
+            model,
+            poly,
+            coeffs,
+            r_squared: 0.0,
+            range,
+        };
+
+        let mean = points.iter().map(|(_, y)| y).sum::<f64>() / points.len() as f64;
+        let ss_tot: f64 = points.iter().map(|(_, y)| (y - mean).powi(2)).sum();
+        let ss_res: f64 = points.iter().map(|&(x, y)| (y - fit.eval(x)).powi(2)).sum();
+        fit.r_squared = if ss_tot > 0.0 {
+            1.0 - ss_res / ss_tot
+        } else {
+            1.0
+        };
+
+        Some(fit)
+    }
+
+    pub fn model(&self) -> Model {
+        self.model
+    }
+
+    ///
+    /// For polynomials, the coefficients from the constant term up.
+    /// For exponential and power models, `[a, b]`.
+    ///
+    /// [`Fit::eval`] uses a centered form of polynomials instead, which is more accurate
+    /// far away from zero.
+    ///
+    pub fn coefficients(&self) -> &[f64] {
+        &self.coeffs
+    }
+
+    ///
+    /// The coefficient of determination of the fit, computed on the original points.
+    ///
+    pub fn r_squared(&self) -> f64 {
+        self.r_squared
+    }
+
+    ///
+    /// The smallest and largest x of the points that were fit.
+    ///
+    pub fn range(&self) -> [f64; 2] {
+        self.range
+    }
+
+    pub fn eval(&self, x: f64) -> f64 {
+        match self.model {
+            Model::Linear | Model::Polynomial(_) => self.poly.eval(x),
+            Model::Exponential => self.coeffs[0] * (self.coeffs[1] * x).exp(),
+            Model::Power => self.coeffs[0] * x.powf(self.coeffs[1]),
+        }
+    }
+
+    ///
+    /// Sample the curve at `num` points over the x range of the data, including both ends.
+    ///
+    pub fn points(&self, num: usize) -> Vec<(f64, f64)> {
+        let [min, max] = self.range;
+        if min >= max || num < 2 {
+            return vec![(min, self.eval(min))];
+        }
+        crate::util::range_iter([min, max], num - 1)
+            .chain(std::iter::once(max))
+            .map(|x| (x, self.eval(x)))
+            .collect()
+    }
+}
+
+///
+/// A polynomial in `t = (x - center) / scale`.
+///
+#[derive(Clone, Debug)]
+struct Scaled {
+    center: f64,
+    scale: f64,
+    coeffs: Vec<f64>,
+}
+
+impl Scaled {
+    fn eval(&self, x: f64) -> f64 {
+        let t = (x - self.center) / self.scale;
+        self.coeffs.iter().rev().fold(0.0, |acc, c| acc * t + c)
+    }
+
+    ///
+    /// The coefficients of the same polynomial in powers of x.
+    ///
+    fn expand(&self) -> Vec<f64> {
+        let n = self.coeffs.len();
+        let mut coeffs = vec![0.0; n];
+
+        //Expand each ((x - center) / scale)^k with the binomial theorem.
+        for (k, ck) in self.coeffs.iter().enumerate() {
+            let ck = ck / self.scale.powi(k as i32);
+            let mut binom = 1.0;
+            for (j, coeff) in coeffs.iter_mut().enumerate().take(k + 1) {
+                *coeff += ck * binom * (-self.center).powi((k - j) as i32);
+                binom = binom * (k - j) as f64 / (j + 1) as f64;
+            }
+        }
+        coeffs
+    }
+}
+
+///
+/// Least squares polynomial fit by solving the normal equations.
+///
+/// The x values are centered and scaled into `[-1, 1]` first so that the normal equations
+/// stay well conditioned for large or far from zero x values like timestamps.
+///
+fn polyfit(points: impl Iterator<Item = (f64, f64)>, degree: usize) -> Option<Scaled> {
+    let points: Vec<_> = points.collect();
+    let n = degree + 1;
+    if points.len() < n {
+        return None;
+    }
+
+    let center = points.iter().map(|&(x, _)| x).sum::<f64>() / points.len() as f64;
+    let scale = points
+        .iter()
+        .map(|&(x, _)| (x - center).abs())
+        .fold(0.0, f64::max);
+    let scale = if scale > 0.0 { scale } else { 1.0 };
+
+    let mut a = vec![vec![0.0; n + 1]; n];
+    for &(x, y) in &points {
+        let t = (x - center) / scale;
+        let pows: Vec<f64> = (0..n).map(|k| t.powi(k as i32)).collect();
+        for i in 0..n {
+            for j in 0..n {
+                a[i][j] += pows[i] * pows[j];
+            }
+            a[i][n] += pows[i] * y;
+        }
+    }
+
+    //Every entry is at most the number of points since t is within [-1, 1].
+    let tolerance = 1e-10 * points.len() as f64;
+
+    //Gaussian elimination with partial pivoting.
+    for col in 0..n {
+        let pivot =
+            (col..n).max_by(|&i, &j| a[i][col].abs().partial_cmp(&a[j][col].abs()).unwrap())?;
+        if a[pivot][col].abs() <= tolerance {
+            return None;
+        }
+        a.swap(col, pivot);
+        let (top, rest) = a.split_at_mut(col + 1);
+        let pivot_row = &top[col];
+        for row in rest {
+            let f = row[col] / pivot_row[col];
+            for (r, p) in row[col..].iter_mut().zip(&pivot_row[col..]) {
+                *r -= f * p;
+            }
+        }
+    }
+
+    let mut coeffs = vec![0.0; n];
+    for row in (0..n).rev() {
+        let s: f64 = (row + 1..n).map(|k| a[row][k] * coeffs[k]).sum();
+        coeffs[row] = (a[row][n] - s) / a[row][row];
+    }
+    Some(Scaled {
+        center,
+        scale,
+        coeffs,
+    })
+}
+
+///
+/// Write a number with about three significant digits.
+///
+struct Short(f64);
+
+impl fmt::Display for Short {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let v = self.0;
+        if v == 0.0 || (1e-3..1e5).contains(&v.abs()) {
+            let digits = (2 - v.abs().log10().floor() as i32).max(0) as usize;
+            write!(f, "{:.*}", if v == 0.0 { 0 } else { digits }, v)
+        } else {
+            write!(f, "{:.2e}", v)
+        }
+    }
+}
+
+impl fmt::Display for Fit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let c = &self.coeffs;
+        write!(f, "y = ")?;
+        match self.model {
+            Model::Linear | Model::Polynomial(_) => {
+                let mut first = true;
+                for (k, &v) in c.iter().enumerate().rev() {
+                    if v == 0.0 && !(first && k == 0) {
+                        continue;
+                    }
+                    if first {
+                        write!(f, "{}", Short(v))?;
+                    } else if v < 0.0 {
+                        write!(f, " - {}", Short(-v))?;
+                    } else {
+                        write!(f, " + {}", Short(v))?;
+                    }
+                    match k {
+                        0 => {}
+                        1 => write!(f, "x")?,
+                        k => write!(f, "x^{}", k)?,
+                    }
+                    first = false;
+                }
+            }
+            Model::Exponential => write!(f, "{}e^({}x)", Short(c[0]), Short(c[1]))?,
+            Model::Power => write!(f, "{}x^{}", Short(c[0]), Short(c[1]))?,
+        }
+        write!(f, " (R² = {:.3})", self.r_squared)
+    }
+}
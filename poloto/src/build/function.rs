@@ -0,0 +1,277 @@
+//!
+//! Plot functions by sampling them more finely where they bend, instead of at a fixed number of points.
+//!
+//! Jumps and asymptotes are replaced with holes so that lines don't draw a
+//! vertical segment across them.
+//!
+
+///
+/// Sampling options shared by [`Adaptive`] and [`Parametric`].
+///
+#[derive(Copy, Clone, Debug)]
+struct Opts {
+    initial: usize,
+    max_depth: usize,
+    tolerance: f64,
+}
+
+impl Default for Opts {
+    fn default() -> Self {
+        Opts {
+            initial: 100,
+            max_depth: 10,
+            tolerance: 1e-3,
+        }
+    }
+}
+
+///
+/// Built with [`adaptive`].
+///
+#[derive(Copy, Clone, Debug)]
+pub struct Adaptive<F> {
+    func: F,
+    range: [f64; 2],
+    y_range: Option<[f64; 2]>,
+    opts: Opts,
+}
+
+///
+/// Sample `y = func(x)` over `range`, adding more points where the curve bends.
+///
+/// Points where the function is not finite, or is far outside of where most of its values lie,
+/// become holes. Use [`Adaptive::with_y_range`] to say where that is instead.
+///
+pub fn adaptive<F: Fn(f64) -> f64>(func: F, range: [f64; 2]) -> Adaptive<F> {
+    assert!(range[1] > range[0]);
+    Adaptive {
+        func,
+        range,
+        y_range: None,
+        opts: Opts::default(),
+    }
+}
+
+///
+/// Built with [`parametric`].
+///
+#[derive(Copy, Clone, Debug)]
+pub struct Parametric<F> {
+    func: F,
+    range: [f64; 2],
+    opts: Opts,
+}
+
+///
+/// Sample the curve `(x, y) = func(t)` for `t` in `range`, adding more points where it bends.
+///
+/// Points that are not finite become holes.
+///
+pub fn parametric<F: Fn(f64) -> (f64, f64)>(func: F, range: [f64; 2]) -> Parametric<F> {
+    assert!(range[1] > range[0]);
+    Parametric {
+        func,
+        range,
+        opts: Opts::default(),
+    }
+}
+
+macro_rules! opts_methods {
+    () => {
+        ///
+        /// How many evenly spaced samples to start with. The default is 100.
+        ///
+        pub fn with_initial_samples(mut self, initial: usize) -> Self {
+            assert!(initial >= 2, "need at least two samples");
+            self.opts.initial = initial;
+            self
+        }
+
+        ///
+        /// How many times each starting interval can be split in half. The default is 10.
+        ///
+        pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+            self.opts.max_depth = max_depth;
+            self
+        }
+
+        ///
+        /// How far the curve may stray from a straight segment, as a fraction of the size
+        /// of the curve. The default of 0.001 is about half a pixel on a normal plot.
+        ///
+        pub fn with_tolerance(mut self, tolerance: f64) -> Self {
+            assert!(tolerance > 0.0, "tolerance must be positive");
+            self.opts.tolerance = tolerance;
+            self
+        }
+    };
+}
+
+impl<F: Fn(f64) -> f64> Adaptive<F> {
+    opts_methods!();
+
+    ///
+    /// Only draw the function where it is inside of this range. Everything else is a hole.
+    ///
+    pub fn with_y_range(mut self, y_range: [f64; 2]) -> Self {
+        assert!(y_range[1] > y_range[0]);
+        self.y_range = Some(y_range);
+        self
+    }
+
+    pub fn points(&self) -> Vec<(f64, f64)> {
+        let f = |x: f64| [x, (self.func)(x)];
+        let limit = match self.y_range {
+            Some(r) => r,
+            None => {
+                //Values past a few times the spread of the typical values are taken to be an asymptote.
+                let [lo, hi] = spread(&initial(&f, self.range, self.opts.initial), 1);
+                let ext = if hi > lo { hi - lo } else { 1.0 };
+                [lo - 3.0 * ext, hi + 3.0 * ext]
+            }
+        };
+        let f = |x: f64| {
+            let [x, y] = f(x);
+            if y >= limit[0] && y <= limit[1] {
+                [x, y]
+            } else {
+                [x, f64::NAN]
+            }
+        };
+        sample(&f, self.range, self.opts)
+    }
+}
+
+impl<F: Fn(f64) -> (f64, f64)> Parametric<F> {
+    opts_methods!();
+
+    pub fn points(&self) -> Vec<(f64, f64)> {
+        let f = |t: f64| {
+            let (x, y) = (self.func)(t);
+            [x, y]
+        };
+        sample(&f, self.range, self.opts)
+    }
+}
+
+fn valid(p: [f64; 2]) -> bool {
+    p[0].is_finite() && p[1].is_finite()
+}
+
+fn initial<F: Fn(f64) -> [f64; 2]>(f: &F, range: [f64; 2], n: usize) -> Vec<(f64, [f64; 2])> {
+    let [a, b] = range;
+    (0..n)
+        .map(|i| {
+            let t = a + (b - a) * i as f64 / (n - 1) as f64;
+            (t, f(t))
+        })
+        .collect()
+}
+
+///
+/// The 5th and 95th percentile of one coordinate of the finite samples.
+///
+fn spread(samples: &[(f64, [f64; 2])], k: usize) -> [f64; 2] {
+    let mut vals: Vec<f64> = samples
+        .iter()
+        .map(|(_, p)| p[k])
+        .filter(|v| v.is_finite())
+        .collect();
+    if vals.is_empty() {
+        return [0.0, 0.0];
+    }
+    vals.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    [0.05, 0.95].map(|q| super::histogram::quantile(&vals, q))
+}
+
+struct Sampler<'a, F> {
+    f: &'a F,
+    scale: [f64; 2],
+    opts: Opts,
+    out: Vec<(f64, f64)>,
+}
+
+impl<'a, F: Fn(f64) -> [f64; 2]> Sampler<'a, F> {
+    fn push(&mut self, p: [f64; 2]) {
+        if valid(p) {
+            self.out.push((p[0], p[1]));
+        } else {
+            self.out.push((p[0], f64::NAN));
+        }
+    }
+
+    ///
+    /// The largest distance between the two points along either axis, relative to the size of the curve.
+    ///
+    fn dist(&self, a: [f64; 2], b: [f64; 2]) -> f64 {
+        ((a[0] - b[0]) / self.scale[0])
+            .abs()
+            .max(((a[1] - b[1]) / self.scale[1]).abs())
+    }
+
+    ///
+    /// Output the points after `a` up to and including `b`.
+    ///
+    fn segment(&mut self, (ta, a): (f64, [f64; 2]), (tb, b): (f64, [f64; 2]), depth: usize) {
+        if !valid(a) && !valid(b) {
+            self.push(b);
+            return;
+        }
+
+        let tm = (ta + tb) / 2.0;
+        let m = (self.f)(tm);
+        let at_max = depth >= self.opts.max_depth;
+
+        if valid(a) && valid(b) && valid(m) {
+            let chord = [(a[0] + b[0]) / 2.0, (a[1] + b[1]) / 2.0];
+            if self.dist(m, chord) <= self.opts.tolerance {
+                self.push(b);
+                return;
+            }
+            if at_max {
+                //Still not smooth this close up, so it's a jump.
+                if self.dist(a, b) > 0.05 {
+                    self.push([m[0], f64::NAN]);
+                } else {
+                    self.push(m);
+                }
+                self.push(b);
+                return;
+            }
+        } else if at_max {
+            //Close in on where the function stops being valid.
+            self.push(m);
+            self.push(b);
+            return;
+        }
+
+        self.segment((ta, a), (tm, m), depth + 1);
+        self.segment((tm, m), (tb, b), depth + 1);
+    }
+}
+
+fn sample<F: Fn(f64) -> [f64; 2]>(f: &F, range: [f64; 2], opts: Opts) -> Vec<(f64, f64)> {
+    let start = initial(f, range, opts.initial);
+
+    let scale = [0, 1].map(|k| {
+        let [lo, hi] = spread(&start, k);
+        if hi > lo {
+            hi - lo
+        } else {
+            1.0
+        }
+    });
+
+    let mut s = Sampler {
+        f,
+        scale,
+        opts,
+        out: vec![],
+    };
+
+    s.push(start[0].1);
+    for w in start.windows(2) {
+        s.segment(w[0], w[1], 0);
+    }
+    s.out
+}
@@ -0,0 +1,162 @@
+//!
+//! Create heatmaps from a grid of values.
+//!
+use super::*;
+use crate::colormap::Colormap;
+
+///
+/// Colormap and value range of a heatmap, used when rendering the cells and the colorbar.
+///
+#[derive(Copy, Clone, Debug)]
+pub struct HeatmapStyle {
+    pub colormap: Colormap,
+    pub range: [f64; 2],
+}
+
+///
+/// One corner of a heatmap cell.
+///
+/// Each cell is made up of two points. The first is the corner with the lowest x and y
+/// and has no value. The second is the opposite corner and carries the value of the cell.
+///
+#[derive(Copy, Clone, Debug)]
+pub struct Cell {
+    pub x: f64,
+    pub y: f64,
+    pub value: Option<f64>,
+}
+
+impl Cell {
+    pub fn new(x: f64, y: f64) -> Self {
+        Cell { x, y, value: None }
+    }
+}
+
+impl Point for Cell {
+    type X = f64;
+    type Y = f64;
+    fn get(&self) -> (&f64, &f64) {
+        (&self.x, &self.y)
+    }
+    fn value(&self) -> Option<f64> {
+        self.value
+    }
+}
+
+impl Unwrapper for Cell {
+    type Item = Cell;
+    fn unwrap(self) -> Cell {
+        self
+    }
+}
+
+///
+/// A grid of values along with the edges of its cells.
+///
+#[derive(Clone, Debug)]
+pub struct Heatmap {
+    xedges: Vec<f64>,
+    yedges: Vec<f64>,
+    values: Vec<f64>,
+    colormap: Colormap,
+    range: Option<[f64; 2]>,
+}
+
+impl Heatmap {
+    ///
+    /// Create a heatmap from rows of values. Row `j` spans `yedges[j]..yedges[j+1]`
+    /// and column `i` spans `xedges[i]..xedges[i+1]`, so there must be one more edge
+    /// than there are rows and columns.
+    ///
+    /// Non finite values are not drawn.
+    ///
+    pub fn new<R: IntoIterator<Item = f64>>(
+        xedges: impl IntoIterator<Item = f64>,
+        yedges: impl IntoIterator<Item = f64>,
+        rows: impl IntoIterator<Item = R>,
+    ) -> Self {
+        let xedges: Vec<_> = xedges.into_iter().collect();
+        let yedges: Vec<_> = yedges.into_iter().collect();
+        assert!(xedges.len() >= 2, "need at least two x edges");
+        assert!(yedges.len() >= 2, "need at least two y edges");
+
+        let mut num_rows = 0;
+        let mut values = vec![];
+        for row in rows {
+            let len = values.len();
+            values.extend(row);
+            assert_eq!(
+                values.len() - len,
+                xedges.len() - 1,
+                "row {} doesn't match the number of x edges",
+                num_rows
+            );
+            num_rows += 1;
+        }
+        assert_eq!(
+            num_rows,
+            yedges.len() - 1,
+            "number of rows doesn't match the number of y edges"
+        );
+
+        Heatmap {
+            xedges,
+            yedges,
+            values,
+            colormap: Colormap::default(),
+            range: None,
+        }
+    }
+
+    pub fn with_colormap(mut self, colormap: Colormap) -> Self {
+        self.colormap = colormap;
+        self
+    }
+
+    ///
+    /// Map the colormap over this range instead of the min and max of the values.
+    ///
+    pub fn with_range(mut self, range: [f64; 2]) -> Self {
+        self.range = Some(range);
+        self
+    }
+
+    pub(crate) fn style(&self) -> HeatmapStyle {
+        let [min, max] = self.range.unwrap_or_else(|| {
+            let mut it = self.values.iter().copied().filter(|x| x.is_finite());
+            let first = it.next().unwrap_or(0.0);
+            it.fold([first, first], |[min, max], x| [min.min(x), max.max(x)])
+        });
+        let range = if min == max {
+            f64::unit_range(Some(min))
+        } else {
+            [min, max]
+        };
+        HeatmapStyle {
+            colormap: self.colormap,
+            range,
+        }
+    }
+
+    pub(crate) fn into_cells(self) -> Vec<Cell> {
+        let Heatmap {
+            xedges,
+            yedges,
+            values,
+            ..
+        } = self;
+
+        let cols = xedges.len() - 1;
+        let mut cells = Vec::with_capacity(values.len() * 2);
+        for (k, value) in values.into_iter().enumerate() {
+            let (i, j) = (k % cols, k / cols);
+            cells.push(Cell::new(xedges[i], yedges[j]));
+            cells.push(Cell {
+                x: xedges[i + 1],
+                y: yedges[j + 1],
+                value: Some(value),
+            });
+        }
+        cells
+    }
+}
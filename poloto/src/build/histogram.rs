@@ -0,0 +1,259 @@
+//!
+//! Bin raw samples into points that can be passed to [`SinglePlotBuilder::histogram`].
+//!
+use super::*;
+
+///
+/// A number that samples can be binned by.
+///
+pub trait BinNum: PlotNum {
+    fn to_f64(self) -> f64;
+
+    fn from_f64(val: f64) -> Self;
+
+    ///
+    /// The range `[lo, hi)` the bins need to cover to include all samples between `min` and `max`.
+    ///
+    fn extent(min: Self, max: Self) -> [f64; 2];
+
+    ///
+    /// Turn a computed bin width into one that makes sense for this type.
+    ///
+    fn snap_width(width: f64) -> f64;
+}
+
+impl BinNum for f64 {
+    fn to_f64(self) -> f64 {
+        self
+    }
+    fn from_f64(val: f64) -> Self {
+        val
+    }
+    fn extent(min: f64, max: f64) -> [f64; 2] {
+        if min == max {
+            [min - 0.5, max + 0.5]
+        } else {
+            [min, max]
+        }
+    }
+    fn snap_width(width: f64) -> f64 {
+        width
+    }
+}
+
+impl BinNum for i128 {
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+    fn from_f64(val: f64) -> Self {
+        val.round() as i128
+    }
+    fn extent(min: i128, max: i128) -> [f64; 2] {
+        [min as f64, (max + 1) as f64]
+    }
+    fn snap_width(width: f64) -> f64 {
+        width.ceil().max(1.0)
+    }
+}
+
+///
+/// How to pick the bin edges.
+///
+#[derive(Clone, Debug)]
+pub enum Bins<T> {
+    /// This many equally wide bins.
+    Count(usize),
+    /// Bins of this width starting at the smallest sample.
+    Width(T),
+    /// `log2(n) + 1` bins.
+    Sturges,
+    /// Bins of width `2 * IQR / cbrt(n)`. Falls back to [`Bins::Sturges`] if the IQR is zero.
+    FreedmanDiaconis,
+    /// These edges, in increasing order. Samples outside of them are dropped.
+    Edges(Vec<T>),
+}
+
+///
+/// What the height of each bar means.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BinNorm {
+    /// The number of samples in the bin.
+    Count,
+    /// The fraction of samples in the bin. The heights add up to one.
+    Probability,
+    /// The fraction of samples divided by the bin width. The areas add up to one.
+    Density,
+}
+
+///
+/// Samples sorted into bins.
+///
+#[derive(Clone, Debug)]
+pub struct Histogram<T> {
+    edges: Vec<T>,
+    counts: Vec<usize>,
+    norm: BinNorm,
+}
+
+impl<T: BinNum> Histogram<T> {
+    ///
+    /// Bin the samples. Non finite samples like NaN or infinity are skipped.
+    ///
+    pub fn new(samples: impl IntoIterator<Item = T>, bins: Bins<T>) -> Self {
+        let mut samples: Vec<f64> = samples
+            .into_iter()
+            .map(T::to_f64)
+            .filter(|v| v.is_finite())
+            .collect();
+        samples.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        let edges = match bins {
+            Bins::Edges(edges) => {
+                assert!(
+                    edges.windows(2).all(|w| w[0] < w[1]),
+                    "bin edges must be increasing"
+                );
+                edges
+            }
+            bins => compute_edges(&samples, bins),
+        };
+
+        let fedges: Vec<f64> = edges.iter().map(|e| e.to_f64()).collect();
+        let mut counts = vec![0; fedges.len().saturating_sub(1)];
+        if let Some(last_bin) = counts.len().checked_sub(1) {
+            let last = fedges[fedges.len() - 1];
+            for v in samples {
+                let i = fedges.partition_point(|&e| e <= v);
+                if i == 0 || v > last {
+                    continue;
+                }
+                //The last bin includes its right edge.
+                counts[(i - 1).min(last_bin)] += 1;
+            }
+        }
+
+        Histogram {
+            edges,
+            counts,
+            norm: BinNorm::Count,
+        }
+    }
+
+    ///
+    /// Set what the height of each bar means. The default is [`BinNorm::Count`].
+    ///
+    pub fn with_norm(mut self, norm: BinNorm) -> Self {
+        self.norm = norm;
+        self
+    }
+
+    ///
+    /// Shorthand for `with_norm(BinNorm::Probability)`.
+    ///
+    pub fn probability(self) -> Self {
+        self.with_norm(BinNorm::Probability)
+    }
+
+    ///
+    /// Shorthand for `with_norm(BinNorm::Density)`.
+    ///
+    pub fn density(self) -> Self {
+        self.with_norm(BinNorm::Density)
+    }
+
+    pub fn edges(&self) -> &[T] {
+        &self.edges
+    }
+
+    pub fn counts(&self) -> &[usize] {
+        &self.counts
+    }
+
+    ///
+    /// The height of each bar.
+    ///
+    pub fn heights(&self) -> Vec<f64> {
+        let total: usize = self.counts.iter().sum();
+        let total = total.max(1) as f64;
+        self.counts
+            .iter()
+            .zip(self.edges.windows(2))
+            .map(|(&c, w)| match self.norm {
+                BinNorm::Count => c as f64,
+                BinNorm::Probability => c as f64 / total,
+                BinNorm::Density => c as f64 / total / (w[1].to_f64() - w[0].to_f64()),
+            })
+            .collect()
+    }
+
+    ///
+    /// The left edge and height of each bar, followed by the right edge of the last bar with a height of zero.
+    ///
+    pub fn points(&self) -> Vec<(T, f64)> {
+        let mut points: Vec<_> = self.edges.iter().copied().zip(self.heights()).collect();
+        if let Some(&last) = self.edges.last() {
+            if !self.counts.is_empty() {
+                points.push((last, 0.0));
+            }
+        }
+        points
+    }
+}
+
+pub(super) fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let pos = q * (sorted.len() - 1) as f64;
+    let (lo, hi) = (pos.floor() as usize, pos.ceil() as usize);
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (pos - lo as f64)
+}
+
+fn compute_edges<T: BinNum>(sorted: &[f64], bins: Bins<T>) -> Vec<T> {
+    let (min, max) = match (sorted.first(), sorted.last()) {
+        (Some(&a), Some(&b)) => (T::from_f64(a), T::from_f64(b)),
+        _ => return vec![],
+    };
+    let [lo, hi] = T::extent(min, max);
+    let range = hi - lo;
+
+    let sturges = ((sorted.len() as f64).log2().ceil() as usize + 1).max(1);
+
+    let (count, width) = match bins {
+        Bins::Count(n) => {
+            assert!(n > 0, "need at least one bin");
+            (Some(n), range / n as f64)
+        }
+        Bins::Sturges => (Some(sturges), range / sturges as f64),
+        Bins::Width(w) => {
+            let w = w.to_f64();
+            assert!(w > 0.0, "bin width must be positive");
+            (None, w)
+        }
+        Bins::FreedmanDiaconis => {
+            let iqr = quantile(sorted, 0.75) - quantile(sorted, 0.25);
+            let w = 2.0 * iqr / (sorted.len() as f64).cbrt();
+            if w > 0.0 {
+                (None, w)
+            } else {
+                (Some(sturges), range / sturges as f64)
+            }
+        }
+        Bins::Edges(_) => unreachable!(),
+    };
+
+    let snapped = T::snap_width(width);
+    let (count, exact) = match count {
+        Some(n) if snapped == width => (n, true),
+        _ => (((range / snapped).ceil() as usize).max(1), false),
+    };
+
+    (0..=count)
+        .map(|i| {
+            //Avoid rounding errors moving the last edge off of the largest sample.
+            if exact && i == count {
+                T::from_f64(hi)
+            } else {
+                T::from_f64(lo + i as f64 * snapped)
+            }
+        })
+        .collect()
+}
@@ -7,16 +7,32 @@ use std::iter::{Fuse, FusedIterator};
 
 use super::*;
 
+pub mod annotate;
 pub mod bar;
+pub mod bubble;
+pub mod candle;
+pub mod contour;
 pub mod crop;
+pub mod distribution;
+pub mod fit;
+pub mod function;
+pub mod heatmap;
+pub mod histogram;
 pub mod output_zip;
+pub mod pie;
+pub mod polar;
+pub mod quiver;
+pub mod refline;
+pub mod rolling;
 pub mod unwrapper;
 use marker::Area;
 
 pub mod marker;
 pub mod plotit;
 
+pub use annotate::annotate;
 use plotit::*;
+pub use refline::{hline, hspan, vline, vspan};
 use unwrapper::Unwrapper;
 
 ///
@@ -30,6 +46,41 @@ pub enum PlotType {
     LineFill,
     LineFillRaw,
     Bars,
+    Heatmap(heatmap::HeatmapStyle),
+    Bubble(bubble::BubbleStyle),
+    Marker(Marker),
+    LineMarker(Marker),
+    Step(Step),
+    LineSmooth,
+    LineFillSmooth,
+    Candle,
+    Quiver(quiver::QuiverStyle),
+}
+
+///
+/// Where the vertical segment of a step plot is drawn between two points.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Step {
+    /// At the x of the first point, so each y value holds to the left of its point.
+    Pre,
+    /// Half way between the two points.
+    Mid,
+    /// At the x of the second point, so each y value holds to the right of its point.
+    Post,
+}
+
+///
+/// The shape drawn at each point of a scatter or line plot.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Marker {
+    Circle,
+    Square,
+    Triangle,
+    Diamond,
+    Cross,
+    Plus,
 }
 
 ///
@@ -39,6 +90,8 @@ pub enum PlotType {
 pub enum PlotMetaType {
     Plot(PlotType),
     Text,
+    Annotation(annotate::AnnotationStyle),
+    RefLine(refline::RefLineKind),
 }
 
 ///
@@ -226,6 +279,9 @@ pub trait PlotIterator {
 
 type DynIt<'a, L, D> = Box<dyn Iterator<Item = PlotTag<L, D>> + 'a>;
 
+type BubbleIt<X, Y, D> = PlotIterCreator<std::vec::IntoIter<bubble::Bubble<X, Y>>, D>;
+type CandleIt<X, Y, D> = PlotIterCreator<std::vec::IntoIter<(X, Y)>, D>;
+
 #[derive(Copy, Clone)]
 pub struct PlotRes<I: Iterator, L: Point> {
     pub(crate) area: Area<L::X, L::Y>,
@@ -247,6 +303,27 @@ pub trait Point {
     type X: PlotNum;
     type Y: PlotNum;
     fn get(&self) -> (&Self::X, &Self::Y);
+
+    ///
+    /// An optional third dimension of the point, like the value of a heatmap cell.
+    ///
+    fn value(&self) -> Option<f64> {
+        None
+    }
+
+    ///
+    /// An optional value that is mapped through a colormap, like the color of a bubble.
+    ///
+    fn color(&self) -> Option<f64> {
+        None
+    }
+
+    ///
+    /// An optional direction at the point, like the vector of a quiver arrow.
+    ///
+    fn vector(&self) -> Option<[f64; 2]> {
+        None
+    }
 }
 impl<X: PlotNum, Y: PlotNum> Point for (X, Y) {
     type X = X;
@@ -312,6 +389,42 @@ where
     ClonedPlotIt::new(it.into_iter())
 }
 
+///
+/// A plot iterator with the given min max bounds. The points are never collected and are
+/// written out as they are iterated over, so large series don't need to fit in memory.
+///
+/// Points outside of the bounds are still drawn, past the edge of the plot area.
+///
+/// Only line, scatter and step plots are written out point by point. Smooth lines,
+/// lines with markers, candlesticks, quivers, LTTB downsampling and path simplification
+/// still collect the points of a plot, or of each segment between holes, into a `Vec`.
+///
+pub fn bounded<L: Point, I: IntoIterator>(
+    it: I,
+    x: [L::X; 2],
+    y: [L::Y; 2],
+) -> BoundedPlotIt<I::IntoIter, L::X, L::Y>
+where
+    I::Item: build::unwrapper::Unwrapper<Item = L>,
+{
+    BoundedPlotIt::new(it.into_iter(), x, y)
+}
+
+///
+/// A plot iterator that is created by calling `func` twice: once to find the min max bounds,
+/// and once to render the points. Neither pass collects the points, so this works for sources
+/// that can be read again but don't fit in memory, like a file.
+///
+/// The render pass has the same limits as [`bounded`]: only line, scatter and step plots
+/// avoid collecting their points.
+///
+pub fn streamed<L: Point, I: IntoIterator, F: FnMut() -> I>(func: F) -> StreamedPlotIt<F>
+where
+    I::Item: build::unwrapper::Unwrapper<Item = L>,
+{
+    StreamedPlotIt::new(func)
+}
+
 // use std::iter::Map;
 // pub struct PlotIterCreator<I:Iterator> where I::Item:Point{
 //     it:std::iter::Chain<std::iter::Chain<std::iter::Once<build::PlotTag<I::Item>>, Map<I, fn(I::Item) -> build::PlotTag<I::Item>>>, std::iter::Once<build::PlotTag<I::Item>>>
@@ -343,6 +456,21 @@ pub struct SinglePlotBuilder<D> {
     label: D,
 }
 
+///
+/// Builder for a plot that draws a smooth curve through its points. Created by [`SinglePlotBuilder::smooth`].
+///
+pub struct SmoothPlotBuilder<D> {
+    label: D,
+}
+
+///
+/// Builder for a plot that draws a shape at each point. Created by [`SinglePlotBuilder::with_marker`].
+///
+pub struct MarkerPlotBuilder<D> {
+    label: D,
+    marker: Marker,
+}
+
 #[derive(Clone)]
 pub struct PlotIterCreator<I, D> {
     start: Option<(PlotMetaType, D)>,
@@ -389,6 +517,25 @@ impl<I: Iterator<Item = L>, L: Point, D: Display> Iterator for PlotIterCreator<I
 }
 
 impl<D: Display> SinglePlotBuilder<D> {
+    ///
+    /// Draw a shape at each point of a scatter or line plot.
+    /// The shapes belong to the `poloto_marker` css class.
+    ///
+    pub fn with_marker(self, marker: Marker) -> MarkerPlotBuilder<D> {
+        MarkerPlotBuilder {
+            label: self.label,
+            marker,
+        }
+    }
+
+    ///
+    /// Draw a smooth curve through the points of a line or line fill plot using cubic beziers.
+    /// The curve is monotone between points so it never overshoots the data.
+    ///
+    pub fn smooth(self) -> SmoothPlotBuilder<D> {
+        SmoothPlotBuilder { label: self.label }
+    }
+
     fn gen<P: PlotIt>(self, it: P, typ: PlotMetaType) -> PlotRes<PlotIterCreator<P::It, D>, P::L> {
         let mut area = Area::new();
         let it = it.unpack(&mut area);
@@ -406,6 +553,13 @@ impl<D: Display> SinglePlotBuilder<D> {
         self.gen(it, PlotMetaType::Plot(PlotType::Line))
     }
 
+    /// Create a step function from plots using a SVG path element made up of
+    /// horizontal and vertical segments.
+    /// The path element belongs to the `poloto_step` and `.poloto[N]stroke` css classes.
+    pub fn step<P: PlotIt>(self, step: Step, it: P) -> PlotRes<PlotIterCreator<P::It, D>, P::L> {
+        self.gen(it, PlotMetaType::Plot(PlotType::Step(step)))
+    }
+
     pub(crate) fn bars<P: PlotIt>(self, it: P) -> PlotRes<PlotIterCreator<P::It, D>, P::L> {
         self.gen(it, PlotMetaType::Plot(PlotType::Bars))
     }
@@ -420,6 +574,8 @@ impl<D: Display> SinglePlotBuilder<D> {
 
     /// Create a histogram from plots using SVG rect elements.
     /// Each bar's left side will line up with a point.
+    /// The last point only marks where the last bar ends.
+    /// Use [`histogram::Histogram`] to bin raw samples.
     /// Each rect element belongs to the `.poloto[N]fill` css class.
     pub fn histogram<P: PlotIt>(self, it: P) -> PlotRes<PlotIterCreator<P::It, D>, P::L> {
         self.gen(it, PlotMetaType::Plot(PlotType::Histo))
@@ -438,6 +594,61 @@ impl<D: Display> SinglePlotBuilder<D> {
         self.gen(it, PlotMetaType::Plot(PlotType::LineFillRaw))
     }
 
+    /// Create a heatmap using SVG rect elements colored by a colormap.
+    /// A colorbar is drawn below the legend.
+    /// Each rect element belongs to the `poloto_cell` css class and contains a `<title>` with its value.
+    pub fn heatmap(
+        self,
+        heatmap: heatmap::Heatmap,
+    ) -> PlotRes<PlotIterCreator<std::vec::IntoIter<heatmap::Cell>, D>, heatmap::Cell> {
+        let style = heatmap.style();
+        self.gen(
+            heatmap.into_cells(),
+            PlotMetaType::Plot(PlotType::Heatmap(style)),
+        )
+    }
+
+    /// Create a bubble chart using SVG circle elements whose area is proportional to the size of each point.
+    /// A legend of sizes is drawn below the legend, as well as a colorbar if a colormap is used.
+    /// Each circle belongs to the `poloto_bubble` css class and contains a `<title>` with its size.
+    pub fn bubble<X: PlotNum, Y: PlotNum>(
+        self,
+        bubbles: bubble::Bubbles<X, Y>,
+    ) -> PlotRes<BubbleIt<X, Y, D>, bubble::Bubble<X, Y>> {
+        let style = bubbles.style();
+        self.gen(
+            bubbles.into_points(),
+            PlotMetaType::Plot(PlotType::Bubble(style)),
+        )
+    }
+
+    /// Create a quiver plot using SVG line elements with an arrowhead `<marker>`.
+    /// A colorbar is drawn below the legend if a colormap is used.
+    /// The lines belong to the `poloto_quiver` css class and contain a `<title>` with their magnitude.
+    pub fn quiver(
+        self,
+        quiver: quiver::Quiver,
+    ) -> PlotRes<PlotIterCreator<std::vec::IntoIter<quiver::Arrow>, D>, quiver::Arrow> {
+        let style = quiver.style();
+        self.gen(
+            quiver.into_points(),
+            PlotMetaType::Plot(PlotType::Quiver(style)),
+        )
+    }
+
+    /// Create a candlestick chart using a SVG line for the wick and a rect for the body of each candle.
+    /// The width of the bodies is picked from the spacing between x values.
+    /// Each candle belongs to either the `poloto_candle_up` or `poloto_candle_down` css class.
+    ///
+    /// Each candle is made up of four points in the order open, high, low, close.
+    pub fn candlestick<X: PlotNum, Y: PlotNum>(
+        self,
+        candles: impl IntoIterator<Item = candle::Candle<X, Y>>,
+    ) -> PlotRes<CandleIt<X, Y, D>, (X, Y)> {
+        let points: Vec<_> = candles.into_iter().flat_map(|c| c.points()).collect();
+        self.gen(points, PlotMetaType::Plot(PlotType::Candle))
+    }
+
     ///
     /// Write some text in the legend. This doesnt increment the plot number.
     ///
@@ -458,6 +669,34 @@ pub fn plot<D: Display>(label: D) -> SinglePlotBuilder<D> {
     SinglePlotBuilder { label }
 }
 
+impl<D: Display> SmoothPlotBuilder<D> {
+    /// Create a smooth line from plots using a SVG path element.
+    /// The path element belongs to the `.poloto[N]stroke` css class.
+    pub fn line<P: PlotIt>(self, it: P) -> PlotRes<PlotIterCreator<P::It, D>, P::L> {
+        plot(self.label).gen(it, PlotMetaType::Plot(PlotType::LineSmooth))
+    }
+
+    /// Create a smooth line from plots that will be filled underneath using a SVG path element.
+    /// The path element belongs to the `.poloto[N]fill` css class.
+    pub fn line_fill<P: PlotIt>(self, it: P) -> PlotRes<PlotIterCreator<P::It, D>, P::L> {
+        plot(self.label).gen(it, PlotMetaType::Plot(PlotType::LineFillSmooth))
+    }
+}
+
+impl<D: Display> MarkerPlotBuilder<D> {
+    /// Create a line from plots using a SVG path element, with a marker at each point.
+    /// The path and the markers belong to the `poloto_marker` and `.poloto[N]stroke` css classes.
+    pub fn line<P: PlotIt>(self, it: P) -> PlotRes<PlotIterCreator<P::It, D>, P::L> {
+        plot(self.label).gen(it, PlotMetaType::Plot(PlotType::LineMarker(self.marker)))
+    }
+
+    /// Create a scatter plot from plots, using a marker at each point.
+    /// The markers belong to the `poloto_marker` and `.poloto[N]fill` css classes.
+    pub fn scatter<P: PlotIt>(self, it: P) -> PlotRes<PlotIterCreator<P::It, D>, P::L> {
+        plot(self.label).gen(it, PlotMetaType::Plot(PlotType::Marker(self.marker)))
+    }
+}
+
 impl<I: IntoIterator<Item = P>, P: PlotIterator<L = L>, L: Point> PlotIterator for I {
     type L = L;
     type P = std::iter::Flatten<std::vec::IntoIter<P::P>>;
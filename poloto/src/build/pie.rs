@@ -0,0 +1,66 @@
+//!
+//! Create pie and donut charts from labeled values.
+//!
+use super::*;
+
+///
+/// A list of labeled slices along with how to draw them.
+///
+#[derive(Clone, Debug)]
+pub struct Pie<D> {
+    slices: Vec<(D, f64)>,
+    inner_radius: f64,
+    percentages: bool,
+}
+
+impl<D: Display> Pie<D> {
+    ///
+    /// Create a pie chart where each slice is sized by its share of the total.
+    ///
+    /// Slices that are not finite or not positive are not drawn.
+    ///
+    pub fn new(slices: impl IntoIterator<Item = (D, f64)>) -> Self {
+        Pie {
+            slices: slices.into_iter().collect(),
+            inner_radius: 0.0,
+            percentages: false,
+        }
+    }
+
+    ///
+    /// Cut out the middle of the pie. The inner radius is a fraction of the outer radius
+    /// and is clamped to `[0,1)`.
+    ///
+    pub fn donut(mut self, inner_radius: f64) -> Self {
+        self.inner_radius = inner_radius.clamp(0.0, 0.99);
+        self
+    }
+
+    ///
+    /// Write the percentage of each slice on top of it.
+    ///
+    pub fn with_percentages(mut self) -> Self {
+        self.percentages = true;
+        self
+    }
+
+    pub(crate) fn inner_radius(&self) -> f64 {
+        self.inner_radius
+    }
+
+    pub(crate) fn percentages(&self) -> bool {
+        self.percentages
+    }
+
+    ///
+    /// The slices along with their value and the fraction of the total they make up.
+    ///
+    pub fn fractions(&self) -> impl Iterator<Item = (&D, f64, f64)> {
+        let value = |v: f64| if v.is_finite() && v > 0.0 { v } else { 0.0 };
+        let total: f64 = self.slices.iter().map(|(_, v)| value(*v)).sum();
+        self.slices.iter().map(move |(d, v)| {
+            let frac = if total > 0.0 { value(*v) / total } else { 0.0 };
+            (d, *v, frac)
+        })
+    }
+}
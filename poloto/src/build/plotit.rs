@@ -39,6 +39,73 @@ where
     }
 }
 
+///
+/// A plot iterator with bounds that are known up front. See [`crate::build::bounded`].
+///
+#[derive(Copy, Clone)]
+pub struct BoundedPlotIt<I, X, Y> {
+    it: I,
+    x: [X; 2],
+    y: [Y; 2],
+}
+
+impl<L: Point, I: Iterator> BoundedPlotIt<I, L::X, L::Y>
+where
+    I::Item: build::unwrapper::Unwrapper<Item = L>,
+{
+    pub fn new(it: I, x: [L::X; 2], y: [L::Y; 2]) -> Self {
+        BoundedPlotIt { it, x, y }
+    }
+}
+
+impl<L: Point, I: Iterator> PlotIt for BoundedPlotIt<I, L::X, L::Y>
+where
+    I::Item: build::unwrapper::Unwrapper<Item = L>,
+{
+    type L = L;
+    type It = build::unwrapper::UnwrapperIter<I>;
+
+    fn unpack(self, area: &mut Area<L::X, L::Y>) -> Self::It {
+        for (x, y) in self.x.iter().zip(self.y.iter()) {
+            area.grow(Some(x), Some(y));
+        }
+        build::unwrapper::UnwrapperIter(self.it)
+    }
+}
+
+///
+/// A plot iterator that is created twice, once to find the bounds and once to render it.
+/// See [`crate::build::streamed`].
+///
+#[derive(Copy, Clone)]
+pub struct StreamedPlotIt<F>(F);
+
+impl<L: Point, I: IntoIterator, F: FnMut() -> I> StreamedPlotIt<F>
+where
+    I::Item: build::unwrapper::Unwrapper<Item = L>,
+{
+    pub fn new(func: F) -> Self {
+        Self(func)
+    }
+}
+
+impl<L: Point, I: IntoIterator, F: FnMut() -> I> PlotIt for StreamedPlotIt<F>
+where
+    I::Item: build::unwrapper::Unwrapper<Item = L>,
+{
+    type L = L;
+    type It = build::unwrapper::UnwrapperIter<I::IntoIter>;
+
+    fn unpack(mut self, area: &mut Area<L::X, L::Y>) -> Self::It {
+        for k in (self.0)() {
+            let l = k.unwrap();
+            let (x, y) = l.get();
+            area.grow(Some(x), Some(y));
+        }
+        build::unwrapper::UnwrapperIter((self.0)().into_iter())
+    }
+}
+
 impl<L: Point, I: IntoIterator> PlotIt for I
 where
     I::Item: build::unwrapper::Unwrapper<Item = L>,
@@ -0,0 +1,81 @@
+//!
+//! Helpers for polar plots, where each point is `(theta, r)`.
+//!
+use super::*;
+use std::f64::consts::TAU;
+
+///
+/// Radar chart with one evenly spaced spoke per category.
+///
+/// Use [`Radar::points`] to turn one value per category into a closed shape,
+/// and [`crate::render::polar::PolarFrame::with_radar`] to label the spokes.
+///
+#[derive(Clone, Debug)]
+pub struct Radar<D> {
+    categories: Vec<D>,
+    degrees: bool,
+}
+
+impl<D: Display> Radar<D> {
+    pub fn new(categories: impl IntoIterator<Item = D>) -> Self {
+        let categories: Vec<_> = categories.into_iter().collect();
+        assert!(!categories.is_empty(), "need at least one category");
+        Radar {
+            categories,
+            degrees: false,
+        }
+    }
+
+    ///
+    /// Give the angles in degrees instead of radians,
+    /// to match a [`crate::render::polar::PolarFrame::with_degrees`] frame.
+    ///
+    pub fn with_degrees(mut self) -> Self {
+        self.degrees = true;
+        self
+    }
+
+    fn full_turn(&self) -> f64 {
+        if self.degrees {
+            360.0
+        } else {
+            TAU
+        }
+    }
+
+    ///
+    /// The angle of the spoke of the `i`th category. In radians unless [`Radar::with_degrees`] is used.
+    ///
+    pub fn angle(&self, i: usize) -> f64 {
+        self.full_turn() * i as f64 / self.categories.len() as f64
+    }
+
+    ///
+    /// Place one value on each spoke. The first point is repeated at the end
+    /// so that lines and fills form a closed shape.
+    ///
+    pub fn points(&self, values: impl IntoIterator<Item = f64>) -> Vec<(f64, f64)> {
+        let mut points: Vec<_> = values
+            .into_iter()
+            .enumerate()
+            .map(|(i, r)| (self.angle(i), r))
+            .collect();
+        assert_eq!(
+            points.len(),
+            self.categories.len(),
+            "need one value per category"
+        );
+        points.push((self.full_turn(), points[0].1));
+        points
+    }
+
+    ///
+    /// The angle and name of each spoke.
+    ///
+    pub fn spokes(&self) -> impl Iterator<Item = (f64, &D)> {
+        self.categories
+            .iter()
+            .enumerate()
+            .map(|(i, d)| (self.angle(i), d))
+    }
+}
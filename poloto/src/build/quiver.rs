@@ -0,0 +1,120 @@
+//!
+//! Create quiver plots that draw an arrow at each point of a vector field.
+//!
+use super::*;
+use crate::colormap::Colormap;
+
+///
+/// Sizing and coloring of a quiver plot, used when rendering the arrows and the colorbar.
+///
+#[derive(Copy, Clone, Debug)]
+pub struct QuiverStyle {
+    pub colormap: Option<Colormap>,
+    pub range: [f64; 2],
+    pub scale: Option<f64>,
+    pub max: f64,
+}
+
+///
+/// A vector at a point. Only the point counts towards the bounds of the plot,
+/// since the length of the arrow is picked on screen.
+///
+#[derive(Copy, Clone, Debug)]
+pub struct Arrow {
+    pub x: f64,
+    pub y: f64,
+    pub dx: f64,
+    pub dy: f64,
+}
+
+impl Point for Arrow {
+    type X = f64;
+    type Y = f64;
+    fn get(&self) -> (&f64, &f64) {
+        (&self.x, &self.y)
+    }
+    fn value(&self) -> Option<f64> {
+        Some(self.dx.hypot(self.dy))
+    }
+    fn vector(&self) -> Option<[f64; 2]> {
+        Some([self.dx, self.dy])
+    }
+}
+
+impl Unwrapper for Arrow {
+    type Item = Arrow;
+    fn unwrap(self) -> Arrow {
+        self
+    }
+}
+
+///
+/// A list of vectors at points, along with how to scale and color the arrows.
+///
+#[derive(Clone, Debug)]
+pub struct Quiver {
+    arrows: Vec<Arrow>,
+    scale: Option<f64>,
+    colormap: Option<Colormap>,
+}
+
+impl Quiver {
+    ///
+    /// Create a quiver plot from `(x, y, dx, dy)` tuples.
+    ///
+    /// Arrows with non finite values are not drawn.
+    ///
+    pub fn new(arrows: impl IntoIterator<Item = (f64, f64, f64, f64)>) -> Self {
+        Quiver {
+            arrows: arrows
+                .into_iter()
+                .map(|(x, y, dx, dy)| Arrow { x, y, dx, dy })
+                .filter(|a| [a.x, a.y, a.dx, a.dy].iter().all(|v| v.is_finite()))
+                .collect(),
+            scale: None,
+            colormap: None,
+        }
+    }
+
+    ///
+    /// Multiply each vector by this to get the length of its arrow relative to the plot area.
+    /// The x part is a fraction of the width of the plot area and the y part a fraction of
+    /// its height, so each axis is scaled on its own regardless of its units.
+    ///
+    /// By default the longest arrow is as long as the smallest spacing between points on screen.
+    /// Arrows are drawn after the plot area is picked, so they can reach past its edges.
+    ///
+    pub fn with_scale(mut self, scale: f64) -> Self {
+        self.scale = Some(scale);
+        self
+    }
+
+    ///
+    /// Color each arrow by its magnitude instead of the color of the plot.
+    ///
+    pub fn with_colormap(mut self, colormap: Colormap) -> Self {
+        self.colormap = Some(colormap);
+        self
+    }
+
+    pub(crate) fn style(&self) -> QuiverStyle {
+        let mut mags = self.arrows.iter().map(|a| a.dx.hypot(a.dy));
+        let first = mags.next().unwrap_or(0.0);
+        let [min, max] = mags.fold([first, first], |[min, max], m| [min.min(m), max.max(m)]);
+        let range = if min == max {
+            f64::unit_range(Some(min))
+        } else {
+            [min, max]
+        };
+        QuiverStyle {
+            colormap: self.colormap,
+            range,
+            scale: self.scale,
+            max,
+        }
+    }
+
+    pub(crate) fn into_points(self) -> Vec<Arrow> {
+        self.arrows
+    }
+}
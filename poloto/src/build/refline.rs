@@ -0,0 +1,133 @@
+//!
+//! Draw horizontal and vertical reference lines and shaded spans across the whole plot area.
+//!
+use super::*;
+
+///
+/// The kind of reference line, used when rendering.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RefLineKind {
+    HLine,
+    VLine,
+    HSpan,
+    VSpan,
+}
+
+#[derive(Copy, Clone, Debug)]
+enum Pos<X, Y> {
+    X(X, X),
+    Y(Y, Y),
+}
+
+///
+/// A reference line or span. Created by [`hline`], [`vline`], [`hspan`] or [`vspan`].
+///
+/// Reference lines don't show up in the legend and don't use up a plot color.
+/// Their label, if any, is written next to them. They only make sense on cartesian frames.
+///
+#[derive(Copy, Clone, Debug)]
+pub struct RefLine<D, X, Y> {
+    label: D,
+    pos: Pos<X, Y>,
+    kind: RefLineKind,
+    grow: bool,
+}
+
+fn new<X: PlotNum, Y: PlotNum>(pos: Pos<X, Y>, kind: RefLineKind) -> RefLine<&'static str, X, Y> {
+    RefLine {
+        label: "",
+        pos,
+        kind,
+        grow: true,
+    }
+}
+
+///
+/// A horizontal line at `y` going across the plot area.
+///
+pub fn hline<X: PlotNum, Y: PlotNum>(y: Y) -> RefLine<&'static str, X, Y> {
+    new(Pos::Y(y, y), RefLineKind::HLine)
+}
+
+///
+/// A vertical line at `x` going across the plot area.
+///
+pub fn vline<X: PlotNum, Y: PlotNum>(x: X) -> RefLine<&'static str, X, Y> {
+    new(Pos::X(x, x), RefLineKind::VLine)
+}
+
+///
+/// A shaded band between `y1` and `y2` going across the plot area.
+///
+pub fn hspan<X: PlotNum, Y: PlotNum>(y1: Y, y2: Y) -> RefLine<&'static str, X, Y> {
+    new(Pos::Y(y1, y2), RefLineKind::HSpan)
+}
+
+///
+/// A shaded band between `x1` and `x2` going across the plot area.
+///
+pub fn vspan<X: PlotNum, Y: PlotNum>(x1: X, x2: X) -> RefLine<&'static str, X, Y> {
+    new(Pos::X(x1, x2), RefLineKind::VSpan)
+}
+
+impl<D: Display, X: PlotNum, Y: PlotNum> RefLine<D, X, Y> {
+    ///
+    /// Write some text next to the line or inside the span.
+    ///
+    pub fn with_label<E: Display>(self, label: E) -> RefLine<E, X, Y> {
+        RefLine {
+            label,
+            pos: self.pos,
+            kind: self.kind,
+            grow: self.grow,
+        }
+    }
+
+    ///
+    /// Whether the line should be kept in view like the points of a plot. The default is true.
+    ///
+    pub fn grow_area(mut self, grow: bool) -> Self {
+        self.grow = grow;
+        self
+    }
+}
+
+impl<D: Display, X: PlotNum, Y: PlotNum> PlotIterator for RefLine<D, X, Y> {
+    type L = (X, Y);
+    type P = PlotIterCreator<std::vec::IntoIter<(X, Y)>, D>;
+    type D = D;
+    fn unpack(self) -> PlotRes<Self::P, Self::L> {
+        let mut area = Area::new();
+
+        //Only one coordinate of each point means anything, the other is a placeholder.
+        let points = match self.pos {
+            Pos::X(a, b) => {
+                if self.grow {
+                    area.grow(Some(&a), None);
+                    area.grow(Some(&b), None);
+                }
+                let y = Y::unit_range(None)[0];
+                vec![(a, y), (b, y)]
+            }
+            Pos::Y(a, b) => {
+                if self.grow {
+                    area.grow(None, Some(&a));
+                    area.grow(None, Some(&b));
+                }
+                let x = X::unit_range(None)[0];
+                vec![(x, a), (x, b)]
+            }
+        };
+
+        PlotRes {
+            area,
+            it: PlotIterCreator::new(
+                self.label,
+                PlotMetaType::RefLine(self.kind),
+                points.into_iter(),
+            ),
+            num_plots: 1,
+        }
+    }
+}
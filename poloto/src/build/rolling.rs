@@ -0,0 +1,282 @@
+//!
+//! Contains the [`Rolling`] and [`Resamplable`] traits which smooth and resample time series.
+//!
+//! Holes (NaN values) are passed through as holes and are not added to any window,
+//! so a gap in the data shows up as a gap in the output instead of turning the
+//! rest of it into NaN.
+//!
+use super::*;
+use std::collections::VecDeque;
+
+fn is_hole<X: PlotNum>(x: &X, y: f64) -> bool {
+    x.is_hole() || y.is_nan()
+}
+
+#[derive(Copy, Clone, Debug)]
+enum Stat {
+    Mean,
+    Min,
+    Max,
+    Percentile(f64),
+}
+
+///
+/// A statistic over the last few values. Created by [`Rolling`].
+///
+#[derive(Clone)]
+pub struct Window<I> {
+    inner: I,
+    stat: Stat,
+    size: usize,
+    window: VecDeque<f64>,
+}
+
+impl<X: PlotNum, I: Iterator> Iterator for Window<I>
+where
+    I::Item: Unwrapper<Item = (X, f64)>,
+{
+    type Item = (X, f64);
+
+    fn next(&mut self) -> Option<(X, f64)> {
+        let (x, y) = self.inner.next()?.unwrap();
+        if is_hole(&x, y) {
+            return Some((x, y));
+        }
+
+        if self.window.len() == self.size {
+            self.window.pop_front();
+        }
+        self.window.push_back(y);
+
+        let w = self.window.iter().copied();
+        let val = match self.stat {
+            Stat::Mean => w.sum::<f64>() / self.window.len() as f64,
+            Stat::Min => w.fold(f64::INFINITY, f64::min),
+            Stat::Max => w.fold(f64::NEG_INFINITY, f64::max),
+            Stat::Percentile(p) => {
+                let mut sorted: Vec<_> = w.collect();
+                sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                histogram::quantile(&sorted, p / 100.0)
+            }
+        };
+        Some((x, val))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+///
+/// Exponential moving average. Created by [`Rolling::exponential_moving_average`].
+///
+#[derive(Clone)]
+pub struct Ema<I> {
+    inner: I,
+    alpha: f64,
+    last: Option<f64>,
+}
+
+impl<X: PlotNum, I: Iterator> Iterator for Ema<I>
+where
+    I::Item: Unwrapper<Item = (X, f64)>,
+{
+    type Item = (X, f64);
+
+    fn next(&mut self) -> Option<(X, f64)> {
+        let (x, y) = self.inner.next()?.unwrap();
+        if is_hole(&x, y) {
+            return Some((x, y));
+        }
+
+        let val = match self.last {
+            Some(last) => self.alpha * y + (1.0 - self.alpha) * last,
+            None => y,
+        };
+        self.last = Some(val);
+        Some((x, val))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+///
+/// How to combine the values that fall in the same slot when resampling.
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Aggregate {
+    Mean,
+    Last,
+}
+
+///
+/// Values resampled onto a regular grid. Created by [`Resamplable::resample`].
+///
+#[derive(Clone)]
+pub struct Resample<I> {
+    inner: I,
+    step: f64,
+    agg: Aggregate,
+    //The slot being filled, the sum, count and last value in it.
+    slot: Option<(i64, f64, usize, f64)>,
+    out: VecDeque<(f64, f64)>,
+}
+
+impl<I> Resample<I> {
+    fn flush(&mut self) {
+        if let Some((k, sum, count, last)) = self.slot.take() {
+            let val = match self.agg {
+                Aggregate::Mean => sum / count as f64,
+                Aggregate::Last => last,
+            };
+            self.out.push_back((k as f64 * self.step, val));
+        }
+    }
+}
+
+impl<I: Iterator> Iterator for Resample<I>
+where
+    I::Item: Unwrapper<Item = (f64, f64)>,
+{
+    type Item = (f64, f64);
+
+    fn next(&mut self) -> Option<(f64, f64)> {
+        while self.out.is_empty() {
+            let (x, y) = match self.inner.next() {
+                Some(g) => g.unwrap(),
+                None => {
+                    self.flush();
+                    break;
+                }
+            };
+
+            if is_hole(&x, y) {
+                self.flush();
+                self.out.push_back((x, y));
+                continue;
+            }
+
+            let k = (x / self.step).floor() as i64;
+            match &mut self.slot {
+                Some((cur, sum, count, last)) if *cur == k => {
+                    *sum += y;
+                    *count += 1;
+                    *last = y;
+                }
+                _ => {
+                    let prev = self.slot.map(|s| s.0);
+                    self.flush();
+                    //Slots without any values become a hole.
+                    if let Some(prev) = prev {
+                        if k > prev + 1 {
+                            self.out
+                                .push_back(((prev + 1) as f64 * self.step, f64::NAN));
+                        }
+                    }
+                    self.slot = Some((k, y, 1, y));
+                }
+            }
+        }
+        self.out.pop_front()
+    }
+}
+
+///
+/// Smooth and resample series of `(x, y)` points sorted by x.
+///
+/// ```rust
+/// use poloto::prelude::Rolling;
+/// let data = [(0.0, 1.0), (1.0, 3.0), (2.0, f64::NAN), (3.0, 5.0)];
+/// let smooth: Vec<_> = data.into_iter().moving_average(2).collect();
+/// assert_eq!(smooth[1], (1.0, 2.0));
+/// assert!(smooth[2].1.is_nan());
+/// assert_eq!(smooth[3], (3.0, 4.0));
+/// ```
+///
+pub trait Rolling<X: PlotNum>: Iterator + Sized
+where
+    Self::Item: Unwrapper<Item = (X, f64)>,
+{
+    ///
+    /// The mean of the last `window` values. The first few outputs average over fewer values.
+    ///
+    fn moving_average(self, window: usize) -> Window<Self> {
+        window_stat(self, Stat::Mean, window)
+    }
+
+    ///
+    /// Each output is `alpha * y + (1 - alpha) * previous`.
+    ///
+    fn exponential_moving_average(self, alpha: f64) -> Ema<Self> {
+        assert!(alpha > 0.0 && alpha <= 1.0, "alpha must be in (0, 1]");
+        Ema {
+            inner: self,
+            alpha,
+            last: None,
+        }
+    }
+
+    ///
+    /// The smallest of the last `window` values.
+    ///
+    fn rolling_min(self, window: usize) -> Window<Self> {
+        window_stat(self, Stat::Min, window)
+    }
+
+    ///
+    /// The largest of the last `window` values.
+    ///
+    fn rolling_max(self, window: usize) -> Window<Self> {
+        window_stat(self, Stat::Max, window)
+    }
+
+    ///
+    /// The given percentile, from 0 to 100, of the last `window` values.
+    ///
+    fn rolling_percentile(self, window: usize, percentile: f64) -> Window<Self> {
+        assert!(
+            (0.0..=100.0).contains(&percentile),
+            "percentile must be between 0 and 100"
+        );
+        window_stat(self, Stat::Percentile(percentile), window)
+    }
+}
+
+fn window_stat<I>(inner: I, stat: Stat, size: usize) -> Window<I> {
+    assert!(size > 0, "window must not be empty");
+    Window {
+        inner,
+        stat,
+        size,
+        window: VecDeque::with_capacity(size),
+    }
+}
+
+impl<X: PlotNum, I: Iterator> Rolling<X> for I where I::Item: Unwrapper<Item = (X, f64)> {}
+
+///
+/// Resample series of `(x, y)` points sorted by x onto a regular grid.
+///
+pub trait Resamplable: Iterator + Sized
+where
+    Self::Item: Unwrapper<Item = (f64, f64)>,
+{
+    ///
+    /// Combine the values in each slot of width `step` into one point at the start of the slot.
+    /// Slots without values in between are output as a hole.
+    ///
+    fn resample(self, step: f64, agg: Aggregate) -> Resample<Self> {
+        assert!(step > 0.0, "step must be positive");
+        Resample {
+            inner: self,
+            step,
+            agg,
+            slot: None,
+            out: VecDeque::new(),
+        }
+    }
+}
+
+impl<I: Iterator> Resamplable for I where I::Item: Unwrapper<Item = (f64, f64)> {}
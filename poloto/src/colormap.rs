@@ -0,0 +1,99 @@
+//!
+//! Map values to colors for plots that encode a third dimension.
+//!
+
+///
+/// A list of colors that values in the range `[0,1]` are linearly interpolated over.
+///
+#[derive(Copy, Clone, Debug)]
+pub struct Colormap {
+    stops: &'static [[u8; 3]],
+}
+
+const VIRIDIS: &[[u8; 3]] = &[
+    [68, 1, 84],
+    [72, 40, 120],
+    [62, 74, 137],
+    [49, 104, 142],
+    [38, 130, 142],
+    [31, 158, 137],
+    [53, 183, 121],
+    [109, 205, 89],
+    [180, 222, 44],
+    [253, 231, 37],
+];
+
+const MAGMA: &[[u8; 3]] = &[
+    [0, 0, 4],
+    [28, 16, 68],
+    [79, 18, 123],
+    [129, 37, 129],
+    [181, 54, 122],
+    [229, 80, 100],
+    [251, 135, 97],
+    [254, 194, 135],
+    [252, 253, 191],
+];
+
+const GRAYSCALE: &[[u8; 3]] = &[[0, 0, 0], [255, 255, 255]];
+
+const BLUE_RED: &[[u8; 3]] = &[[59, 76, 192], [221, 221, 221], [180, 4, 38]];
+
+impl Default for Colormap {
+    fn default() -> Self {
+        Colormap::viridis()
+    }
+}
+
+impl Colormap {
+    ///
+    /// Create a colormap from a list of at least one rgb color.
+    ///
+    pub const fn new(stops: &'static [[u8; 3]]) -> Self {
+        assert!(!stops.is_empty(), "colormap needs at least one color");
+        Colormap { stops }
+    }
+    pub const fn viridis() -> Self {
+        Colormap::new(VIRIDIS)
+    }
+    pub const fn magma() -> Self {
+        Colormap::new(MAGMA)
+    }
+    pub const fn grayscale() -> Self {
+        Colormap::new(GRAYSCALE)
+    }
+    ///
+    /// Diverging colormap that is gray in the middle.
+    ///
+    pub const fn blue_red() -> Self {
+        Colormap::new(BLUE_RED)
+    }
+
+    ///
+    /// Get the color at `t`, which is clamped to `[0,1]`.
+    ///
+    pub fn color(&self, t: f64) -> [u8; 3] {
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        let last = self.stops.len() - 1;
+        let pos = t * last as f64;
+        let i = (pos.floor() as usize).min(last);
+        let j = (i + 1).min(last);
+        let frac = pos - i as f64;
+
+        let [a, b] = [self.stops[i], self.stops[j]];
+        let lerp = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * frac).round() as u8;
+        [lerp(a[0], b[0]), lerp(a[1], b[1]), lerp(a[2], b[2])]
+    }
+
+    ///
+    /// Get the color of `val` normalized to the range.
+    ///
+    pub fn color_in(&self, val: f64, [min, max]: [f64; 2]) -> [u8; 3] {
+        let t = if max > min {
+            (val - min) / (max - min)
+        } else {
+            0.5
+        };
+        self.color(t)
+    }
+}
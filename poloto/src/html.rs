@@ -0,0 +1,160 @@
+//!
+//! Assemble several figures into one self-contained html document.
+//!
+//! Each figure is rendered by the report inside of a [`crate::header()`] without a theme.
+//! The theme css is written once in the document head and applies to every figure.
+//! By default [`Theme::auto`] is used, which switches to the dark theme when the reader
+//! prefers a dark color scheme.
+//!
+//! The ids inside of each figure are prefixed with `poloto_fig[N]_` so that they don't collide.
+//!
+//! ```rust
+//! use poloto::build::plot;
+//! let mut report = poloto::html::Report::new("my report");
+//!
+//! let data = poloto::frame_build()
+//!     .data(plot("a").line([[0, 0], [1, 1]]))
+//!     .build_and_label(("title", "x", "y"));
+//!
+//! report.add_figure("First", "Some caption", data).unwrap();
+//! let _html = report.render_string().unwrap();
+//! ```
+//!
+use super::*;
+use tagu::stack::ElemOuter;
+
+const PAGE_STYLE: &str = "body{font-family:Roboto,sans-serif;max-width:900px;margin:auto;padding:0 20px;background:white;color:black;}
+figure{margin:0 0 50px 0;}
+figure svg{width:100%;height:auto;}
+@media (prefers-color-scheme: dark) {
+body{background:#1a1a1a;color:white;}
+}";
+
+///
+/// A labeled plot that can be added to a [`Report`].
+///
+pub trait Figure: ElemOuter {
+    ///
+    /// Replace the prefix of the ids in the plot. See [`RenderFrameBuilder::with_id_prefix`].
+    ///
+    fn set_id_prefix(&mut self, prefix: &str);
+}
+
+struct Entry {
+    heading: String,
+    caption: String,
+    svg: String,
+}
+
+///
+/// Builder for a html document made up of several figures.
+///
+pub struct Report {
+    title: String,
+    theme: Theme<'static>,
+    styles: Vec<String>,
+    figures: Vec<Entry>,
+}
+
+impl Report {
+    pub fn new<D: Display>(title: D) -> Self {
+        Report {
+            title: title.to_string(),
+            theme: Theme::auto(),
+            styles: vec![],
+            figures: vec![],
+        }
+    }
+
+    ///
+    /// Use a different theme for the whole document.
+    ///
+    pub fn with_theme(&mut self, theme: Theme<'static>) -> &mut Self {
+        self.theme = theme;
+        self
+    }
+
+    ///
+    /// Add css that is written in the document head after the theme.
+    /// It applies to every figure.
+    ///
+    pub fn add_style<D: Display>(&mut self, css: D) -> &mut Self {
+        self.styles.push(css.to_string());
+        self
+    }
+
+    ///
+    /// Render a figure and add it to the document.
+    ///
+    pub fn add_figure<D1: Display, D2: Display, F: Figure>(
+        &mut self,
+        heading: D1,
+        caption: D2,
+        mut figure: F,
+    ) -> fmt::Result {
+        figure.set_id_prefix(&format!("poloto_fig{}_", self.figures.len()));
+
+        let mut svg = String::new();
+        tagu::render(crate::header().append(figure), &mut svg)?;
+
+        self.figures.push(Entry {
+            heading: heading.to_string(),
+            caption: caption.to_string(),
+            svg,
+        });
+        Ok(())
+    }
+
+    pub fn render_fmt_write<T: fmt::Write>(&self, mut w: T) -> fmt::Result {
+        writeln!(w, "<!DOCTYPE html>")?;
+        writeln!(w, "<html>")?;
+        writeln!(w, "<head>")?;
+        writeln!(w, "<meta charset=\"utf-8\">")?;
+        writeln!(
+            w,
+            "<meta name=\"viewport\" content=\"width=device-width, initial-scale=1.0\">"
+        )?;
+        writeln!(w, "<title>{}</title>", escape(&self.title))?;
+        writeln!(w, "<style>")?;
+        writeln!(w, "{}", PAGE_STYLE)?;
+        writeln!(w, "{}", self.theme.get_str())?;
+        for s in self.styles.iter() {
+            writeln!(w, "{}", s)?;
+        }
+        writeln!(w, "</style>")?;
+        writeln!(w, "</head>")?;
+        writeln!(w, "<body>")?;
+        writeln!(w, "<h1>{}</h1>", escape(&self.title))?;
+
+        for (i, f) in self.figures.iter().enumerate() {
+            writeln!(w, "<figure id=\"poloto_fig{}\">", i)?;
+            if !f.heading.is_empty() {
+                writeln!(w, "<h2>{}</h2>", escape(&f.heading))?;
+            }
+            writeln!(w, "{}", f.svg)?;
+            if !f.caption.is_empty() {
+                writeln!(w, "<figcaption>{}</figcaption>", escape(&f.caption))?;
+            }
+            writeln!(w, "</figure>")?;
+        }
+        writeln!(w, "</body>")?;
+        writeln!(w, "</html>")
+    }
+
+    pub fn render_io_write<T: std::io::Write>(&self, w: T) -> fmt::Result {
+        self.render_fmt_write(tagu::tools::upgrade_write(w))
+    }
+
+    pub fn render_string(&self) -> Result<String, fmt::Error> {
+        let mut s = String::new();
+        self.render_fmt_write(&mut s)?;
+        Ok(s)
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
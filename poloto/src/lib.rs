@@ -43,6 +43,9 @@ mod test_readme {
 use std::fmt;
 
 pub mod build;
+pub mod colormap;
+#[cfg(feature = "html")]
+pub mod html;
 pub mod plotnum;
 pub mod render;
 pub mod ticks;
@@ -59,6 +62,7 @@ use tagu::prelude::*;
 pub mod prelude {
     pub use super::build::crop::Croppable;
     pub use super::build::output_zip::OutputZip;
+    pub use super::build::rolling::{Resamplable, Rolling};
     pub use super::build::PlotIterator;
     pub use super::plots;
 }
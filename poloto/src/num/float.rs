@@ -12,6 +12,7 @@ impl DiscNum for f64 {
 
 pub struct FloatTickFmt;
 
+#[derive(Copy, Clone)]
 pub struct FloatFmt {
     offset: Option<f64>,
     axis: Axis,
@@ -11,6 +11,7 @@ fn round_up_to_nearest_multiple(val: i128, multiple: i128) -> i128 {
 
 pub struct IntegerTickFmt;
 
+#[derive(Copy, Clone)]
 pub struct IntFmt {
     offset: Option<i128>,
     axis: Axis,
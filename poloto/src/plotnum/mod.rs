@@ -32,6 +32,21 @@ pub trait PlotNum: PartialOrd + Copy + std::fmt::Debug {
     fn scale(&self, range: &[Self; 2], max: f64) -> f64;
 
     fn unit_range(offset: Option<Self>) -> [Self; 2];
+
+    ///
+    /// Write the value out in full, for exporting the data behind a plot.
+    /// Numbers should be written as plain numbers and times as ISO 8601 timestamps.
+    ///
+    fn write_value(&self, w: &mut dyn std::fmt::Write) -> std::fmt::Result {
+        write!(w, "{:?}", self)
+    }
+
+    ///
+    /// If the values are points in time, which exporters like Vega-Lite treat differently from numbers.
+    ///
+    fn is_time() -> bool {
+        false
+    }
 }
 
 pub trait HasDefaultTicks: Sized {
@@ -0,0 +1,193 @@
+//!
+//! Reduce the number of points of line plots once they have been mapped to pixels,
+//! so the size of the svg is bounded by the width of the plot instead of the number of points.
+//!
+//! Points are assumed to be sorted by x. Holes split a line into segments that are
+//! downsampled separately, so they are still drawn as gaps.
+//!
+//! Min-max decimation only looks at one pixel column at a time, so it doesn't hold on to
+//! the whole series. LTTB needs all the points of each segment.
+//!
+use crate::build::PlotType;
+use std::collections::VecDeque;
+
+///
+/// How to downsample line plots. See [`crate::render::RenderFrameBuilder::with_downsample`].
+///
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Downsample {
+    /// Largest-Triangle-Three-Buckets, keeping about one point per pixel column.
+    Lttb,
+    /// Keep the first, last, smallest and largest point of each pixel column.
+    MinMax,
+}
+
+pub(super) enum Points<I> {
+    Raw(I),
+    Reduced(std::vec::IntoIter<[f64; 2]>),
+    Columns(Columns<I>),
+}
+
+impl<I: Iterator<Item = [f64; 2]>> Iterator for Points<I> {
+    type Item = [f64; 2];
+    fn next(&mut self) -> Option<[f64; 2]> {
+        match self {
+            Points::Raw(it) => it.next(),
+            Points::Reduced(it) => it.next(),
+            Points::Columns(it) => it.next(),
+        }
+    }
+}
+
+///
+/// Min-max decimation, one pixel column at a time.
+///
+pub(super) struct Columns<I> {
+    it: I,
+    column: Vec<[f64; 2]>,
+    out: VecDeque<[f64; 2]>,
+    done: bool,
+}
+
+impl<I: Iterator<Item = [f64; 2]>> Iterator for Columns<I> {
+    type Item = [f64; 2];
+    fn next(&mut self) -> Option<[f64; 2]> {
+        while self.out.is_empty() && !self.done {
+            match self.it.next() {
+                Some(p) if p[0].is_finite() && p[1].is_finite() => {
+                    if let Some(first) = self.column.first() {
+                        if first[0].floor() != p[0].floor() {
+                            min_max(&self.column, &mut self.out);
+                            self.column.clear();
+                        }
+                    }
+                    self.column.push(p);
+                }
+                p => {
+                    min_max(&self.column, &mut self.out);
+                    self.column.clear();
+                    match p {
+                        Some(hole) => self.out.push_back(hole),
+                        None => self.done = true,
+                    }
+                }
+            }
+        }
+        self.out.pop_front()
+    }
+}
+
+///
+/// Downsample the points if asked to and the plot is a line or a fill.
+///
+pub(super) fn apply<I: Iterator<Item = [f64; 2]>>(
+    mode: Option<Downsample>,
+    p_type: PlotType,
+    it: I,
+) -> Points<I> {
+    let mode = match (mode, p_type) {
+        (
+            Some(mode),
+            PlotType::Line
+            | PlotType::LineFill
+            | PlotType::LineFillRaw
+            | PlotType::LineSmooth
+            | PlotType::LineFillSmooth,
+        ) => mode,
+        _ => return Points::Raw(it),
+    };
+
+    if mode == Downsample::MinMax {
+        return Points::Columns(Columns {
+            it,
+            column: vec![],
+            out: VecDeque::new(),
+            done: false,
+        });
+    }
+
+    let mut out = vec![];
+    let mut segment = vec![];
+    for p in it {
+        if p[0].is_finite() && p[1].is_finite() {
+            segment.push(p);
+        } else {
+            reduce(&segment, &mut out);
+            segment.clear();
+            out.push(p);
+        }
+    }
+    reduce(&segment, &mut out);
+
+    Points::Reduced(out.into_iter())
+}
+
+fn reduce(points: &[[f64; 2]], out: &mut Vec<[f64; 2]>) {
+    let span = points.last().map_or(0.0, |l| (l[0] - points[0][0]).abs());
+    lttb(points, span.ceil() as usize + 1, out)
+}
+
+fn lttb(points: &[[f64; 2]], threshold: usize, out: &mut Vec<[f64; 2]>) {
+    let n = points.len();
+    if threshold < 3 || threshold >= n {
+        out.extend_from_slice(points);
+        return;
+    }
+
+    //The first and last points are always kept. The rest are split into equal buckets.
+    let every = (n - 2) as f64 / (threshold - 2) as f64;
+    let mut a = 0;
+    out.push(points[0]);
+
+    for i in 0..threshold - 2 {
+        let start = (i as f64 * every) as usize + 1;
+        let end = (((i + 1) as f64 * every) as usize + 1).min(n - 1);
+
+        //The average of the next bucket, or the last point for the last bucket.
+        let next_end = (((i + 2) as f64 * every) as usize + 1).min(n);
+        let next = &points[end..next_end.max(end + 1)];
+        let avg = next
+            .iter()
+            .fold([0.0, 0.0], |[x, y], p| [x + p[0], y + p[1]])
+            .map(|v| v / next.len() as f64);
+
+        //Keep the point that forms the largest triangle with the last kept point and the average.
+        let [ax, ay] = points[a];
+        let best = (start..end.max(start + 1))
+            .max_by(|&j, &k| {
+                let area =
+                    |p: [f64; 2]| ((ax - avg[0]) * (p[1] - ay) - (ax - p[0]) * (avg[1] - ay)).abs();
+                area(points[j]).partial_cmp(&area(points[k])).unwrap()
+            })
+            .unwrap();
+
+        out.push(points[best]);
+        a = best;
+    }
+
+    out.push(points[n - 1]);
+}
+
+///
+/// Keep the first, last, smallest and largest point of a column, in their original order.
+///
+fn min_max(column: &[[f64; 2]], out: &mut VecDeque<[f64; 2]>) {
+    if column.is_empty() {
+        return;
+    }
+
+    let by_y =
+        |a: &(usize, &[f64; 2]), b: &(usize, &[f64; 2])| a.1[1].partial_cmp(&b.1[1]).unwrap();
+    let min = column.iter().enumerate().min_by(by_y).unwrap().0;
+    let max = column.iter().enumerate().max_by(by_y).unwrap().0;
+
+    let mut keep = [0, min, max, column.len() - 1];
+    keep.sort_unstable();
+    let mut last = None;
+    for k in keep {
+        if last != Some(k) {
+            out.push_back(column[k]);
+            last = Some(k);
+        }
+    }
+}
@@ -0,0 +1,740 @@
+//!
+//! Export the data behind a plot as CSV, JSON or a Vega-Lite specification.
+//!
+use super::*;
+use crate::build::{Marker, PlotMetaType, PlotTag, PlotType, Step};
+use crate::ticks::tick_fmt::TickFmt;
+
+///
+/// Determine how plot values are written out when exporting.
+///
+#[derive(Copy, Clone, Debug)]
+pub enum DataFmt {
+    /// Write values in full using [`PlotNum::write_value`].
+    Raw,
+    /// Write values the way the ticks of their axis are labeled, using its [`TickFmt`].
+    /// This rounds them to the precision of the ticks. If the ticks are written relative
+    /// to an offset, like `j+2` where `j=1000`, the offset is exported as well.
+    Ticks,
+}
+
+///
+/// The exported values of one plot. Holes are represented with `None`.
+/// `values` has one entry per point and holds the optional third dimension,
+/// like the value of a heatmap cell. `vectors` holds the direction at each point,
+/// like the vector of a quiver arrow.
+///
+#[derive(Clone, Debug)]
+pub struct SeriesData {
+    pub name: String,
+    pub typ: PlotMetaType,
+    pub points: Vec<[Option<String>; 2]>,
+    pub values: Vec<Option<f64>>,
+    pub vectors: Vec<Option<[f64; 2]>>,
+}
+
+///
+/// The exported values of all plots.
+///
+#[derive(Clone, Debug, Default)]
+pub struct PlotData {
+    series: Vec<SeriesData>,
+    wheres: [Option<String>; 2],
+}
+
+impl PlotData {
+    pub fn series(&self) -> &[SeriesData] {
+        &self.series
+    }
+
+    ///
+    /// The offsets of the x and y axis, like `where j=1000`, if the values were
+    /// written relative to them. Only set when exporting with [`DataFmt::Ticks`].
+    ///
+    pub fn wheres(&self) -> [Option<&str>; 2] {
+        let [x, y] = &self.wheres;
+        [x.as_deref(), y.as_deref()]
+    }
+
+    ///
+    /// Write one row per point with the columns `name,type,x,y`.
+    /// If any point has a value, a `value` column is added,
+    /// and if any point has a vector, `dx` and `dy` columns are added.
+    /// Holes are written as empty cells.
+    /// The offset of an axis is added to the name of its column, like `x where j=1000`.
+    ///
+    pub fn write_csv(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        let has_values = self.has_values();
+        let has_vectors = self.has_vectors();
+        write!(w, "name,type,")?;
+        for (i, (axis, wher)) in ["x", "y"].iter().zip(self.wheres()).enumerate() {
+            if i != 0 {
+                write!(w, ",")?;
+            }
+            match wher {
+                Some(wher) => write_csv_field(w, &format!("{} {}", axis, wher))?,
+                None => write!(w, "{}", axis)?,
+            }
+        }
+        if has_values {
+            write!(w, ",value")?;
+        }
+        if has_vectors {
+            write!(w, ",dx,dy")?;
+        }
+        writeln!(w)?;
+        for s in self.series.iter() {
+            let rows = s.points.iter().zip(s.values.iter()).zip(s.vectors.iter());
+            for (([x, y], v), d) in rows {
+                write_csv_field(w, &s.name)?;
+                write!(w, ",{},", type_name(s.typ))?;
+                write_csv_field(w, x.as_deref().unwrap_or(""))?;
+                write!(w, ",")?;
+                write_csv_field(w, y.as_deref().unwrap_or(""))?;
+                if has_values {
+                    write!(w, ",")?;
+                    if let Some(v) = v {
+                        write!(w, "{:?}", v)?;
+                    }
+                }
+                if has_vectors {
+                    match d {
+                        Some([dx, dy]) => write!(w, ",{:?},{:?}", dx, dy)?,
+                        None => write!(w, ",,")?,
+                    }
+                }
+                writeln!(w)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn has_values(&self) -> bool {
+        self.series
+            .iter()
+            .any(|s| s.values.iter().any(|v| v.is_some()))
+    }
+
+    fn has_vectors(&self) -> bool {
+        self.series
+            .iter()
+            .any(|s| s.vectors.iter().any(|v| v.is_some()))
+    }
+
+    ///
+    /// Write an object of the form `{"series":[{"name":..,"type":..,"points":[[x,y],..]},..]}`.
+    /// Values that are valid numbers are written as json numbers, holes as `null`,
+    /// and anything else as a string. Points that have a value are written as `[x,y,value]`
+    /// and points that have a vector as `[x,y,value,dx,dy]`.
+    /// The offsets of the axes are written as `"x_where"` and `"y_where"` if there are any.
+    ///
+    /// The characters `<`, `>` and `&` are always escaped so that the
+    /// output can be embedded in a svg as is.
+    ///
+    pub fn write_json(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        write!(w, "{{")?;
+        for (axis, wher) in ["x", "y"].iter().zip(self.wheres()) {
+            if let Some(wher) = wher {
+                write!(w, "\"{}_where\":", axis)?;
+                write_json_str(w, wher)?;
+                write!(w, ",")?;
+            }
+        }
+        write!(w, "\"series\":[")?;
+        for (i, s) in self.series.iter().enumerate() {
+            if i != 0 {
+                write!(w, ",")?;
+            }
+            write!(w, "{{\"name\":")?;
+            write_json_str(w, &s.name)?;
+            write!(w, ",\"type\":\"{}\",\"points\":[", type_name(s.typ))?;
+            let points = s.points.iter().zip(s.values.iter()).zip(s.vectors.iter());
+            for (j, (([x, y], v), d)) in points.enumerate() {
+                if j != 0 {
+                    write!(w, ",")?;
+                }
+                write!(w, "[")?;
+                write_json_value(w, x.as_deref())?;
+                write!(w, ",")?;
+                write_json_value(w, y.as_deref())?;
+                if v.is_some() || d.is_some() {
+                    write!(w, ",")?;
+                    write_json_value(w, v.map(|v| format!("{:?}", v)).as_deref())?;
+                }
+                if let Some([dx, dy]) = d {
+                    write!(w, ",{:?},{:?}", dx, dy)?;
+                }
+                write!(w, "]")?;
+            }
+            write!(w, "]}}")?;
+        }
+        write!(w, "]}}")
+    }
+
+    ///
+    /// Render the values as a html `<table>` with the columns `name,x,y`.
+    /// Useful as a fallback for screen readers alongside the svg.
+    /// Holes are rendered as empty cells.
+    ///
+    pub fn html_table(&self) -> impl Elem + Locked + '_ {
+        let head = hbuild::elem("thead").append(
+            hbuild::elem("tr").append(
+                table_cell("th", "name")
+                    .chain(table_cell("th", "x"))
+                    .chain(table_cell("th", "y")),
+            ),
+        );
+
+        let rows = hbuild::from_iter(self.series.iter().flat_map(|s| {
+            s.points.iter().map(move |[x, y]| {
+                hbuild::elem("tr").append(
+                    table_cell("td", &s.name)
+                        .chain(table_cell("td", x.as_deref().unwrap_or("")))
+                        .chain(table_cell("td", y.as_deref().unwrap_or(""))),
+                )
+            })
+        }));
+
+        hbuild::elem("table")
+            .with(("class", "poloto_table"))
+            .append(head)
+            .append(hbuild::elem("tbody").append(rows))
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut s = String::new();
+        self.write_csv(&mut s).unwrap();
+        s
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut s = String::new();
+        self.write_json(&mut s).unwrap();
+        s
+    }
+}
+
+///
+/// A `<metadata>` element with the data as JSON. The JSON escapes the characters that are
+/// special in xml itself, so it is written as is and can be read back without an xml parser.
+///
+pub(crate) struct Metadata(pub String);
+
+impl Locked for Metadata {}
+impl Elem for Metadata {
+    type Tail = tagu::elem::ElementTail<&'static str>;
+    fn render_head(self, w: elem::ElemWrite) -> Result<Self::Tail, fmt::Error> {
+        hbuild::elem("metadata")
+            .with(("class", "poloto_data"))
+            .append(hbuild::raw_escapable(self.0))
+            .render_head(w)
+    }
+}
+
+fn table_cell<'a>(tag: &'static str, val: &'a str) -> impl Elem + Locked + 'a {
+    hbuild::elem(tag).append(hbuild::raw(val)).inline()
+}
+
+fn type_name(typ: PlotMetaType) -> &'static str {
+    match typ {
+        PlotMetaType::Plot(p) => match p {
+            PlotType::Scatter | PlotType::Marker(_) => "scatter",
+            PlotType::Line | PlotType::LineMarker(_) => "line",
+            PlotType::Histo => "histogram",
+            PlotType::LineFill => "line_fill",
+            PlotType::LineFillRaw => "line_fill_raw",
+            PlotType::Bars => "bars",
+            PlotType::Heatmap(_) => "heatmap",
+            PlotType::Bubble(_) => "bubble",
+            PlotType::Step(_) => "step",
+            PlotType::LineSmooth => "line_smooth",
+            PlotType::LineFillSmooth => "line_fill_smooth",
+            PlotType::Candle => "candlestick",
+            PlotType::Quiver(_) => "quiver",
+        },
+        PlotMetaType::Text => "text",
+        PlotMetaType::Annotation(_) => "annotation",
+        PlotMetaType::RefLine(_) => "refline",
+    }
+}
+
+fn write_csv_field(w: &mut dyn fmt::Write, s: &str) -> fmt::Result {
+    if s.contains([',', '"', '\n', '\r']) {
+        write!(w, "\"{}\"", s.replace('"', "\"\""))
+    } else {
+        write!(w, "{}", s)
+    }
+}
+
+fn write_json_str(w: &mut dyn fmt::Write, s: &str) -> fmt::Result {
+    write!(w, "\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => write!(w, "\\\"")?,
+            '\\' => write!(w, "\\\\")?,
+            '\n' => write!(w, "\\n")?,
+            '\r' => write!(w, "\\r")?,
+            '\t' => write!(w, "\\t")?,
+            '<' | '>' | '&' => write!(w, "\\u{:04x}", c as u32)?,
+            c if c.is_control() => write!(w, "\\u{:04x}", c as u32)?,
+            c => write!(w, "{}", c)?,
+        }
+    }
+    write!(w, "\"")
+}
+
+fn write_json_value(w: &mut dyn fmt::Write, s: Option<&str>) -> fmt::Result {
+    match s {
+        None => write!(w, "null"),
+        Some(s) => {
+            //Only the subset of number literals that json accepts.
+            let is_number = s.parse::<f64>().map(|x| x.is_finite()).unwrap_or(false)
+                && !s.starts_with('+')
+                && !s.starts_with('.')
+                && !s.ends_with('.');
+            if is_number {
+                write!(w, "{}", s)
+            } else {
+                write_json_str(w, s)
+            }
+        }
+    }
+}
+
+///
+/// Records the values of plots as they are iterated.
+///
+pub(crate) struct Recorder<'a, X, Y> {
+    fmt: DataFmt,
+    xfmt: &'a dyn TickFmt<X>,
+    yfmt: &'a dyn TickFmt<Y>,
+    data: PlotData,
+}
+
+impl<'a, X: PlotNum, Y: PlotNum> Recorder<'a, X, Y> {
+    pub fn new(fmt: DataFmt, xfmt: &'a dyn TickFmt<X>, yfmt: &'a dyn TickFmt<Y>) -> Self {
+        let mut data = PlotData::default();
+        if let DataFmt::Ticks = fmt {
+            let mut xwher = String::new();
+            let mut ywher = String::new();
+            //Writing to a string can't fail.
+            xfmt.write_where(&mut xwher).unwrap();
+            yfmt.write_where(&mut ywher).unwrap();
+            data.wheres = [xwher, ywher].map(|w| (!w.is_empty()).then_some(w));
+        }
+        Recorder {
+            fmt,
+            xfmt,
+            yfmt,
+            data,
+        }
+    }
+
+    pub fn start(&mut self, name: String, typ: PlotMetaType) {
+        self.data.series.push(SeriesData {
+            name,
+            typ,
+            points: vec![],
+            values: vec![],
+            vectors: vec![],
+        });
+    }
+
+    pub fn push<L: Point<X = X, Y = Y>>(&mut self, l: &L) {
+        let fmt = self.fmt;
+        let (x, y) = l.get();
+        let x = format_value(fmt, self.xfmt, x);
+        let y = format_value(fmt, self.yfmt, y);
+        let series = self.data.series.last_mut().expect("expected start");
+        series.points.push([x, y]);
+        series.values.push(l.value());
+        series.vectors.push(l.vector());
+    }
+
+    pub fn finish(self) -> PlotData {
+        self.data
+    }
+}
+
+fn format_value<N: PlotNum>(fmt: DataFmt, tick_fmt: &dyn TickFmt<N>, val: &N) -> Option<String> {
+    if val.is_hole() {
+        return None;
+    }
+    let mut s = String::new();
+    match fmt {
+        DataFmt::Raw => val.write_value(&mut s).unwrap(),
+        DataFmt::Ticks => tick_fmt.write_tick(&mut s, val).unwrap(),
+    }
+    Some(s)
+}
+
+///
+/// Iterate over all the plots and record their values.
+/// Reference lines are skipped since one coordinate of their points is only a placeholder.
+///
+pub(crate) fn record<X: PlotNum, Y: PlotNum, L: Point<X = X, Y = Y>, P: PlotIterator<L = L>>(
+    plots: P,
+    recorder: &mut Recorder<X, Y>,
+) {
+    let mut skip = false;
+    for tag in plots.unpack().it {
+        match tag {
+            PlotTag::Start { name, typ, .. } => {
+                skip = matches!(typ, PlotMetaType::RefLine(_));
+                if !skip {
+                    recorder.start(name.to_string(), typ)
+                }
+            }
+            PlotTag::Plot(l) => {
+                if !skip {
+                    recorder.push(&l);
+                }
+            }
+            PlotTag::Finish() => {}
+        }
+    }
+}
+
+///
+/// Axis information of a Vega-Lite specification. All values are already formatted.
+///
+pub(crate) struct VegaAxis {
+    pub title: String,
+    pub temporal: bool,
+    pub domain: [String; 2],
+    pub ticks: Vec<String>,
+}
+
+impl VegaAxis {
+    fn typ(&self) -> &'static str {
+        if self.temporal {
+            "temporal"
+        } else {
+            "quantitative"
+        }
+    }
+}
+
+fn vega_shape(marker: Marker) -> &'static str {
+    match marker {
+        Marker::Circle => "circle",
+        Marker::Square => "square",
+        Marker::Triangle => "triangle-up",
+        Marker::Diamond => "diamond",
+        Marker::Cross => "M-1,-1L1,1M1,-1L-1,1",
+        Marker::Plus => "cross",
+    }
+}
+
+fn vega_mark(typ: PlotType) -> String {
+    let mark = match typ {
+        PlotType::Line => "{\"type\":\"line\"}",
+        PlotType::Scatter => "{\"type\":\"point\",\"filled\":true}",
+        PlotType::Histo => "{\"type\":\"bar\"}",
+        PlotType::Bars => "{\"type\":\"bar\",\"orient\":\"horizontal\"}",
+        PlotType::LineFill | PlotType::LineFillRaw => "{\"type\":\"area\"}",
+        PlotType::Heatmap(_) => "{\"type\":\"rect\"}",
+        PlotType::Bubble(_) => "{\"type\":\"circle\"}",
+        PlotType::LineSmooth => "{\"type\":\"line\",\"interpolate\":\"monotone\"}",
+        PlotType::LineFillSmooth => "{\"type\":\"area\",\"interpolate\":\"monotone\"}",
+        PlotType::Candle => unreachable!("candles are written by write_vega_candles"),
+        PlotType::Quiver(_) => "{\"type\":\"point\",\"shape\":\"arrow\",\"filled\":true}",
+        PlotType::Step(Step::Pre) => "{\"type\":\"line\",\"interpolate\":\"step-before\"}",
+        PlotType::Step(Step::Mid) => "{\"type\":\"line\",\"interpolate\":\"step\"}",
+        PlotType::Step(Step::Post) => "{\"type\":\"line\",\"interpolate\":\"step-after\"}",
+        PlotType::Marker(m) => {
+            return format!(
+                "{{\"type\":\"point\",\"filled\":true,\"shape\":\"{}\"}}",
+                vega_shape(m)
+            )
+        }
+        PlotType::LineMarker(m) => {
+            return format!(
+                "{{\"type\":\"line\",\"point\":{{\"filled\":true,\"shape\":\"{}\"}}}}",
+                vega_shape(m)
+            )
+        }
+    };
+    mark.to_string()
+}
+
+fn write_vega_encoding(w: &mut dyn fmt::Write, field: &str, axis: &VegaAxis) -> fmt::Result {
+    write!(
+        w,
+        "{{\"field\":\"{}\",\"type\":\"{}\",\"title\":",
+        field,
+        axis.typ()
+    )?;
+    write_json_str(w, &axis.title)?;
+    write!(w, ",\"scale\":{{\"domain\":[")?;
+    write_json_value(w, Some(&axis.domain[0]))?;
+    write!(w, ",")?;
+    write_json_value(w, Some(&axis.domain[1]))?;
+    write!(w, "]}},\"axis\":{{\"values\":[")?;
+    for (i, t) in axis.ticks.iter().enumerate() {
+        if i != 0 {
+            write!(w, ",")?;
+        }
+        write_json_value(w, Some(t))?;
+    }
+    write!(w, "]}}}}")
+}
+
+///
+/// Write the rest of a heatmap layer. Each cell is made up of two
+/// consecutive points, the second of which has the value.
+///
+fn write_vega_pairs(
+    w: &mut dyn fmt::Write,
+    s: &SeriesData,
+    color_range: Option<[f64; 2]>,
+    xaxis: &VegaAxis,
+    yaxis: &VegaAxis,
+) -> fmt::Result {
+    let pairs = s
+        .points
+        .chunks_exact(2)
+        .zip(s.values.chunks_exact(2).map(|v| v[1]));
+    for (j, (ends, v)) in pairs.enumerate() {
+        if j != 0 {
+            write!(w, ",")?;
+        }
+        let [[x, y], [x2, y2]] = [&ends[0], &ends[1]];
+        write!(w, "{{\"x\":")?;
+        write_json_value(w, x.as_deref())?;
+        write!(w, ",\"y\":")?;
+        write_json_value(w, y.as_deref())?;
+        write!(w, ",\"x2\":")?;
+        write_json_value(w, x2.as_deref())?;
+        write!(w, ",\"y2\":")?;
+        write_json_value(w, y2.as_deref())?;
+        write!(w, ",\"value\":")?;
+        write_json_value(w, v.map(|v| format!("{:?}", v)).as_deref())?;
+        write!(w, "}}")?;
+    }
+    write!(w, "]}},\"encoding\":{{\"x\":")?;
+    write_vega_encoding(w, "x", xaxis)?;
+    write!(w, ",\"y\":")?;
+    write_vega_encoding(w, "y", yaxis)?;
+    write!(
+        w,
+        ",\"x2\":{{\"field\":\"x2\"}},\"y2\":{{\"field\":\"y2\"}}"
+    )?;
+    if let Some([min, max]) = color_range {
+        write!(
+            w,
+            ",\"color\":{{\"field\":\"value\",\"type\":\"quantitative\",\"scale\":{{\"domain\":[{:?},{:?}]}}",
+            min, max
+        )?;
+        if !s.name.is_empty() {
+            write!(w, ",\"title\":")?;
+            write_json_str(w, &s.name)?;
+        }
+        write!(w, "}}")?;
+    } else if !s.name.is_empty() {
+        write!(w, ",\"color\":{{\"datum\":")?;
+        write_json_str(w, &s.name)?;
+        write!(w, "}}")?;
+    }
+    write!(w, "}}}}")
+}
+
+///
+/// Write the rest of a quiver layer. Each arrow is drawn as an arrow shaped point that is
+/// rotated to the direction of its vector and sized by its magnitude.
+///
+fn write_vega_arrows(
+    w: &mut dyn fmt::Write,
+    s: &SeriesData,
+    color_range: Option<[f64; 2]>,
+    xaxis: &VegaAxis,
+    yaxis: &VegaAxis,
+) -> fmt::Result {
+    let arrows = s
+        .points
+        .iter()
+        .zip(s.vectors.iter())
+        .filter_map(|(p, d)| Some((p, (*d)?)));
+    for (j, ([x, y], [dx, dy])) in arrows.enumerate() {
+        if j != 0 {
+            write!(w, ",")?;
+        }
+        //Vega angles are in degrees clockwise from pointing up.
+        let angle = dx.atan2(dy).to_degrees().rem_euclid(360.0);
+        write!(w, "{{\"x\":")?;
+        write_json_value(w, x.as_deref())?;
+        write!(w, ",\"y\":")?;
+        write_json_value(w, y.as_deref())?;
+        write!(
+            w,
+            ",\"dx\":{:?},\"dy\":{:?},\"angle\":{:?},\"magnitude\":{:?}}}",
+            dx,
+            dy,
+            angle,
+            dx.hypot(dy)
+        )?;
+    }
+    write!(w, "]}},\"encoding\":{{\"x\":")?;
+    write_vega_encoding(w, "x", xaxis)?;
+    write!(w, ",\"y\":")?;
+    write_vega_encoding(w, "y", yaxis)?;
+    write!(
+        w,
+        ",\"angle\":{{\"field\":\"angle\",\"type\":\"quantitative\",\"scale\":{{\"domain\":[0,360],\"range\":[0,360]}}}}"
+    )?;
+    write!(
+        w,
+        ",\"size\":{{\"field\":\"magnitude\",\"type\":\"quantitative\"}}"
+    )?;
+    if let Some([min, max]) = color_range {
+        write!(
+            w,
+            ",\"color\":{{\"field\":\"magnitude\",\"type\":\"quantitative\",\"scale\":{{\"domain\":[{:?},{:?}]}}",
+            min, max
+        )?;
+        if !s.name.is_empty() {
+            write!(w, ",\"title\":")?;
+            write_json_str(w, &s.name)?;
+        }
+        write!(w, "}}")?;
+    } else if !s.name.is_empty() {
+        write!(w, ",\"color\":{{\"datum\":")?;
+        write_json_str(w, &s.name)?;
+        write!(w, "}}")?;
+    }
+    write!(w, "}}}}")
+}
+
+///
+/// Write a candlestick layer made up of a rule for the wicks and a bar for the bodies.
+/// Each candle is made up of four consecutive points, open, high, low and close.
+///
+fn write_vega_candles(
+    w: &mut dyn fmt::Write,
+    s: &SeriesData,
+    xaxis: &VegaAxis,
+    yaxis: &VegaAxis,
+) -> fmt::Result {
+    write!(w, "{{\"data\":{{\"values\":[")?;
+    for (j, c) in s.points.chunks_exact(4).enumerate() {
+        if j != 0 {
+            write!(w, ",")?;
+        }
+        write!(w, "{{\"x\":")?;
+        write_json_value(w, c[0][0].as_deref())?;
+        for (k, field) in ["open", "high", "low", "close"].iter().enumerate() {
+            write!(w, ",\"{}\":", field)?;
+            write_json_value(w, c[k][1].as_deref())?;
+        }
+        write!(w, "}}")?;
+    }
+    write!(w, "]}},\"encoding\":{{\"x\":")?;
+    write_vega_encoding(w, "x", xaxis)?;
+    write!(
+        w,
+        ",\"color\":{{\"condition\":{{\"test\":\"datum.close >= datum.open\",\"value\":\"green\"}},\"value\":\"red\"}}}}"
+    )?;
+    write!(w, ",\"layer\":[{{\"mark\":\"rule\",\"encoding\":{{\"y\":")?;
+    write_vega_encoding(w, "low", yaxis)?;
+    write!(
+        w,
+        ",\"y2\":{{\"field\":\"high\"}}}}}},{{\"mark\":\"bar\",\"encoding\":{{\"y\":{{\"field\":\"open\",\"type\":\"{}\"}},\"y2\":{{\"field\":\"close\"}}}}}}]}}",
+        yaxis.typ()
+    )
+}
+
+///
+/// Write a Vega-Lite specification with one layer per plot and the data inlined.
+///
+pub(crate) fn write_vega_lite(
+    w: &mut dyn fmt::Write,
+    data: &PlotData,
+    title: &str,
+    dim: [f64; 2],
+    [xaxis, yaxis]: [VegaAxis; 2],
+) -> fmt::Result {
+    write!(
+        w,
+        "{{\"$schema\":\"https://vega.github.io/schema/vega-lite/v5.json\",\"title\":"
+    )?;
+    write_json_str(w, title)?;
+    write!(w, ",\"width\":{},\"height\":{},\"layer\":[", dim[0], dim[1])?;
+
+    let layers = data.series.iter().filter_map(|s| match s.typ {
+        PlotMetaType::Plot(p) => Some((s, p)),
+        PlotMetaType::Text | PlotMetaType::Annotation(_) | PlotMetaType::RefLine(_) => None,
+    });
+
+    for (i, (s, p)) in layers.enumerate() {
+        if i != 0 {
+            write!(w, ",")?;
+        }
+        if let PlotType::Candle = p {
+            write_vega_candles(w, s, &xaxis, &yaxis)?;
+            continue;
+        }
+
+        write!(w, "{{\"mark\":{},\"data\":{{\"values\":[", vega_mark(p))?;
+
+        match p {
+            PlotType::Heatmap(style) => {
+                write_vega_pairs(w, s, Some(style.range), &xaxis, &yaxis)?;
+                continue;
+            }
+            PlotType::Quiver(style) => {
+                let range = style.colormap.map(|_| style.range);
+                write_vega_arrows(w, s, range, &xaxis, &yaxis)?;
+                continue;
+            }
+            _ => {}
+        }
+
+        //The last point of a histogram only marks where the last bar ends.
+        let num = match p {
+            PlotType::Histo => s.points.len().saturating_sub(1),
+            _ => s.points.len(),
+        };
+        for (j, [x, y]) in s.points.iter().take(num).enumerate() {
+            if j != 0 {
+                write!(w, ",")?;
+            }
+            write!(w, "{{\"x\":")?;
+            write_json_value(w, x.as_deref())?;
+            write!(w, ",\"y\":")?;
+            write_json_value(w, y.as_deref())?;
+
+            //Each histogram bar spans until the next point.
+            if let PlotType::Histo = p {
+                write!(w, ",\"x2\":")?;
+                let next = s.points.get(j + 1).and_then(|[x, _]| x.as_deref());
+                write_json_value(w, next)?;
+            }
+            if let PlotType::Bubble(_) = p {
+                write!(w, ",\"size\":")?;
+                let size = s.values.get(j).copied().flatten();
+                write_json_value(w, size.map(|v| format!("{:?}", v)).as_deref())?;
+            }
+            write!(w, "}}")?;
+        }
+        write!(w, "]}},\"encoding\":{{\"x\":")?;
+        write_vega_encoding(w, "x", &xaxis)?;
+        write!(w, ",\"y\":")?;
+        write_vega_encoding(w, "y", &yaxis)?;
+        if let PlotType::Histo = p {
+            write!(w, ",\"x2\":{{\"field\":\"x2\"}}")?;
+        }
+        if let PlotType::Bubble(_) = p {
+            write!(
+                w,
+                ",\"size\":{{\"field\":\"size\",\"type\":\"quantitative\"}}"
+            )?;
+        }
+        if !s.name.is_empty() {
+            write!(w, ",\"color\":{{\"datum\":")?;
+            write_json_str(w, &s.name)?;
+            write!(w, "}}")?;
+        }
+        write!(w, "}}}}")?;
+    }
+    write!(w, "]}}")
+}
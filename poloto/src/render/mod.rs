@@ -5,8 +5,15 @@
 use crate::build::{PlotIterator, PlotRes, Point};
 
 use super::*;
+pub mod downsample;
+pub mod export;
+pub mod pie;
+pub mod polar;
 mod render_base;
 mod render_plot;
+mod simplify;
+
+use export::{DataFmt, PlotData};
 
 ///
 /// Specify options for the svg plots
@@ -20,6 +27,9 @@ pub struct RenderFrameBuilder {
     ytick_lines: bool,
     precision: usize,
     bar_width: f64,
+    downsample: Option<downsample::Downsample>,
+    simplify: Option<f64>,
+    id_prefix: String,
 }
 
 impl Default for RenderFrameBuilder {
@@ -32,6 +42,9 @@ impl Default for RenderFrameBuilder {
             ytick_lines: false,
             precision: 2,
             bar_width: 20.0,
+            downsample: None,
+            simplify: None,
+            id_prefix: "poloto_".to_string(),
         }
     }
 }
@@ -87,6 +100,36 @@ impl RenderFrameBuilder {
         self
     }
 
+    ///
+    /// Reduce the number of points of line and fill plots to about one per pixel column.
+    /// Off by default.
+    ///
+    pub fn with_downsample(&mut self, mode: downsample::Downsample) -> &mut Self {
+        self.downsample = Some(mode);
+        self
+    }
+
+    ///
+    /// Shrink the paths of line and fill plots. Points that look the same at the configured
+    /// precision are dropped, and so are points within `tolerance` pixels of a straight line.
+    /// Line segments are written with relative coordinates when that is shorter. Off by default.
+    ///
+    pub fn with_simplify(&mut self, tolerance: f64) -> &mut Self {
+        self.simplify = Some(tolerance);
+        self
+    }
+
+    ///
+    /// The prefix of the ids of the elements in the svg. Defaults to `poloto_`.
+    ///
+    /// Give each figure its own prefix when several svgs are inlined in the same html
+    /// document so that their ids don't collide.
+    ///
+    pub fn with_id_prefix(&mut self, prefix: &str) -> &mut Self {
+        self.id_prefix = prefix.to_string();
+        self
+    }
+
     #[deprecated]
     pub fn move_into(&mut self) -> Self {
         self.clone()
@@ -170,6 +213,9 @@ impl RenderFrameBuilder {
             ytick_lines: self.ytick_lines,
             precision: self.precision,
             bar_width: self.bar_width,
+            downsample: self.downsample,
+            simplify: self.simplify,
+            id_prefix: self.id_prefix.clone(),
         }
     }
 }
@@ -194,6 +240,9 @@ pub struct RenderFrame {
     ytick_lines: bool,
     precision: usize,
     bar_width: f64,
+    downsample: Option<downsample::Downsample>,
+    simplify: Option<f64>,
+    id_prefix: String,
 }
 
 impl RenderFrame {
@@ -207,6 +256,23 @@ impl RenderFrame {
     {
         render::Stage1::from_parts(plots, X::default_ticks(), Y::default_ticks(), self)
     }
+
+    ///
+    /// Draw a pie chart instead of plots. There are no axes or ticks.
+    ///
+    pub fn pie<D: Display>(self, pie: build::pie::Pie<D>) -> pie::PieFrame<D> {
+        pie::PieFrame::new(self, pie)
+    }
+
+    ///
+    /// Draw the plots on polar axes. Each point is `(theta, r)` where theta is in radians.
+    ///
+    pub fn polar<L: Point<X = f64, Y = f64>, J: build::PlotIterator<L = L>>(
+        self,
+        plots: J,
+    ) -> polar::PolarFrame<PlotRes<J::P, L>> {
+        polar::PolarFrame::new(self, plots)
+    }
 }
 
 #[deprecated]
@@ -378,9 +444,26 @@ impl<
         Stage3 {
             data: self,
             base: fmt,
+            metadata: None,
         }
     }
 
+    ///
+    /// Collect the values of all plots so that they can be exported as CSV or JSON.
+    ///
+    pub fn plot_data(&self, fmt: DataFmt) -> PlotData
+    where
+        P: Clone,
+        A: Clone,
+        B: Clone,
+    {
+        let xticks = self.xticks.clone().unwrap();
+        let yticks = self.yticks.clone().unwrap();
+        let mut recorder = export::Recorder::new(fmt, &xticks.fmt, &yticks.fmt);
+        export::record(self.plots.clone(), &mut recorder);
+        recorder.finish()
+    }
+
     pub fn boundx(&self) -> &DataBound<X> {
         &self.boundx
     }
@@ -430,6 +513,7 @@ impl<
 pub struct Stage3<P: PlotIterator, A, B, BB> {
     data: Stage2<P, A, B>,
     base: BB,
+    metadata: Option<DataFmt>,
 }
 
 impl<X: PlotNum, Y: PlotNum, L: Point<X = X, Y = Y>, P, A, B, BB> Stage3<P, A, B, BB>
@@ -446,6 +530,120 @@ where
     pub fn headless(self) -> Stage4<Self> {
         Stage4(self)
     }
+
+    ///
+    /// Embed the values of all plots as JSON inside a `<metadata>` element
+    /// so that the svg is self-describing. See [`PlotData::write_json`].
+    ///
+    pub fn with_metadata(self, fmt: DataFmt) -> Self {
+        Stage3 {
+            data: self.data,
+            base: self.base,
+            metadata: Some(fmt),
+        }
+    }
+
+    ///
+    /// Collect the values of all plots so that they can be exported as CSV or JSON.
+    ///
+    pub fn plot_data(&self, fmt: DataFmt) -> PlotData
+    where
+        P: Clone,
+        A: Clone,
+        B: Clone,
+    {
+        self.data.plot_data(fmt)
+    }
+
+    ///
+    /// Write a [Vega-Lite](https://vega.github.io/vega-lite/) specification of the plots
+    /// with the data inlined. Each plot becomes one layer and the axes use the same
+    /// titles, bounds and tick values as the svg.
+    /// Values are written in full, and time axes are temporal with ISO 8601 timestamps.
+    ///
+    pub fn write_vega_lite(&self, w: &mut dyn fmt::Write) -> fmt::Result
+    where
+        P: Clone,
+        A: Clone,
+        B: Clone,
+    {
+        let data = self.data.plot_data(DataFmt::Raw);
+
+        let mut title = String::new();
+        self.base.write_title(&mut title)?;
+
+        let mut xaxis = export::VegaAxis {
+            title: String::new(),
+            temporal: X::is_time(),
+            domain: [
+                vega_value(&self.data.boundx.min)?,
+                vega_value(&self.data.boundx.max)?,
+            ],
+            ticks: self
+                .data
+                .xticks
+                .clone()
+                .unwrap()
+                .iter
+                .into_iter()
+                .filter(|x| *x >= self.data.boundx.min && *x <= self.data.boundx.max)
+                .map(|x| vega_value(&x))
+                .collect::<Result<_, _>>()?,
+        };
+        self.base.write_xname(&mut xaxis.title)?;
+
+        let mut yaxis = export::VegaAxis {
+            title: String::new(),
+            temporal: Y::is_time(),
+            domain: [
+                vega_value(&self.data.boundy.min)?,
+                vega_value(&self.data.boundy.max)?,
+            ],
+            ticks: self
+                .data
+                .yticks
+                .clone()
+                .unwrap()
+                .iter
+                .into_iter()
+                .filter(|y| *y >= self.data.boundy.min && *y <= self.data.boundy.max)
+                .map(|y| vega_value(&y))
+                .collect::<Result<_, _>>()?,
+        };
+        self.base.write_yname(&mut yaxis.title)?;
+
+        let dim = [self.data.opt.boundx.max, self.data.opt.boundy.max];
+
+        export::write_vega_lite(w, &data, &title, dim, [xaxis, yaxis])
+    }
+
+    ///
+    /// Shorthand for [`Stage3::write_vega_lite`] into a string.
+    ///
+    pub fn vega_lite(&self) -> Result<String, fmt::Error>
+    where
+        P: Clone,
+        A: Clone,
+        B: Clone,
+    {
+        let mut s = String::new();
+        self.write_vega_lite(&mut s)?;
+        Ok(s)
+    }
+}
+
+#[cfg(feature = "html")]
+impl<X: PlotNum, Y: PlotNum, L: Point<X = X, Y = Y>, P, A, B, BB> crate::html::Figure
+    for Stage3<P, A, B, BB>
+where
+    P: PlotIterator<L = L>,
+    A: crate::ticks::TickDist<Num = X>,
+    B: crate::ticks::TickDist<Num = Y>,
+    BB: BaseFmt,
+{
+    fn set_id_prefix(&mut self, prefix: &str) {
+        self.data.opt.id_prefix = prefix.to_string();
+    }
 }
 
 use tagu::stack::*;
@@ -460,32 +658,106 @@ where
         mut self,
         mut writer: ElemStack<'a, Sentinel>,
     ) -> Result<ElemStack<'a, Sentinel>, fmt::Error> {
+        let id_prefix = &self.data.opt.id_prefix;
+
+        let mut title = String::new();
+        self.base.write_title(&mut title)?;
+        writer.put(
+            hbuild::elem("title")
+                .with(("id", format_move!("{}title", id_prefix)))
+                .append(hbuild::raw(title))
+                .inline(),
+        )?;
+
         writer.put(hbuild::single("circle").with(attrs!(
             ("r", "1e5"),
             ("class", "poloto_background"),
             ("fill", "white")
         )))?;
 
-        let writer = render::render_plot::render_plot(
+        let xticks = self.data.xticks.unwrap();
+        let yticks = self.data.yticks.unwrap();
+
+        let mut recorder = self
+            .metadata
+            .map(|fmt| export::Recorder::new(fmt, &xticks.fmt, &yticks.fmt));
+
+        let (writer, plot_names) = render::render_plot::render_plot(
             writer,
             &self.data.boundx,
             &self.data.boundy,
             &self.data.opt,
             self.data.plots,
+            recorder.as_mut(),
         )?;
 
-        render::render_base::render_base(
+        let metadata = recorder.map(|r| r.finish().to_json());
+
+        let mut writer = render::render_base::render_base(
             writer,
-            self.data.xticks,
-            self.data.yticks,
+            xticks,
+            yticks,
             &self.data.boundx,
             &self.data.boundy,
             &mut self.base,
             &self.data.opt,
-        )
+        )?;
+
+        let mut desc = String::new();
+        write_summary(
+            &mut desc,
+            &self.base,
+            &self.data.boundx,
+            &self.data.boundy,
+            &plot_names,
+        )?;
+        writer.put(
+            hbuild::elem("desc")
+                .with(("id", format_move!("{}desc", id_prefix)))
+                .append(hbuild::raw(desc))
+                .inline(),
+        )?;
+
+        if let Some(metadata) = metadata {
+            writer.put(export::Metadata(metadata))?;
+        }
+        Ok(writer)
     }
 }
 
+fn vega_value<N: PlotNum>(val: &N) -> Result<String, fmt::Error> {
+    let mut s = String::new();
+    val.write_value(&mut s)?;
+    Ok(s)
+}
+
+///
+/// Write a short description of the plots for screen readers.
+///
+fn write_summary<X: PlotNum, Y: PlotNum>(
+    w: &mut dyn fmt::Write,
+    base: &dyn BaseFmt,
+    boundx: &DataBound<X>,
+    boundy: &DataBound<Y>,
+    plot_names: &[String],
+) -> fmt::Result {
+    let mut xname = String::new();
+    base.write_xname(&mut xname)?;
+    let mut yname = String::new();
+    base.write_yname(&mut yname)?;
+
+    write!(
+        w,
+        "x axis {} from {:?} to {:?}. y axis {} from {:?} to {:?}.",
+        xname, boundx.min, boundx.max, yname, boundy.min, boundy.max
+    )?;
+
+    if !plot_names.is_empty() {
+        write!(w, " Plots: {}.", plot_names.join(", "))?;
+    }
+    Ok(())
+}
+
 impl<A, B, C> BaseFmt for (A, B, C)
 where
     A: Display,
@@ -537,6 +809,9 @@ impl<R: Elem> Elem for Stage4<R> {
 ///
 /// Default svg header
 ///
+/// The svg has the `img` role. It is named and described by the `<title>` and `<desc>`
+/// elements that the plot writes as its children.
+///
 #[derive(Copy, Clone)]
 pub struct Header<A> {
     dim: [f64; 2],
@@ -605,6 +880,9 @@ impl<A: Attr> Header<A> {
     pub fn dark_theme(self) -> elem::Append<Self, Theme<'static>> {
         self.append(Theme::dark())
     }
+    pub fn auto_theme(self) -> elem::Append<Self, Theme<'static>> {
+        self.append(Theme::auto())
+    }
 }
 
 impl<A> Locked for Header<A> {}
@@ -620,6 +898,7 @@ impl<A: Attr> Elem for Header<A> {
                 format_move!("{} {} {} {}", 0, 0, self.viewbox[0], self.viewbox[1])
             ),
             ("xmlns", "http://www.w3.org/2000/svg"),
+            ("role", "img"),
             self.attr
         ));
 
@@ -635,86 +914,166 @@ pub struct Theme<'a> {
     styles: &'a str,
 }
 
-impl Theme<'static> {
-    pub const fn light() -> Theme<'static> {
-        /// Default light theme
-
-        const STYLE_CONFIG_LIGHT_DEFAULT: &str = ".poloto{
+///
+/// Css rules shared by all the themes. They don't set any colors.
+///
+macro_rules! shared_rules {
+    () => {
+        ".poloto{
   stroke-linecap:round;
   stroke-linejoin:round;
   font-family:Roboto,sans-serif;
   font-size:16px;
 }
-.poloto_background{fill:AliceBlue;}
 .poloto_scatter{stroke-width:7}
 .poloto_line{stroke-width:2}
-.poloto_text{fill: black;}
+.poloto_marker{stroke-width:2}
+.poloto_candle{stroke-width:2}
+.poloto_quiver{stroke-width:1.5}
 .poloto_name{font-size:24px;dominant-baseline:auto;text-anchor:middle;}
 .poloto_where{dominant-baseline:middle;text-anchor:start}
 .poloto_text.poloto_legend{font-size:20px;dominant-baseline:middle;text-anchor:start;}
 .poloto_text.poloto_ticks.poloto_y{dominant-baseline:middle;text-anchor:end}
 .poloto_text.poloto_ticks.poloto_x{dominant-baseline:auto;text-anchor:middle}
-.poloto_imgs.poloto_ticks{stroke: black;stroke-width:3;fill:none;stroke-dasharray:none}
-.poloto_grid{stroke:gray;stroke-width:0.5}
-
-.poloto0.poloto_stroke{stroke:blue;}
-.poloto1.poloto_stroke{stroke:red;}
-.poloto2.poloto_stroke{stroke:green;}
-.poloto3.poloto_stroke{stroke:gold;}
-.poloto4.poloto_stroke{stroke:aqua;}
-.poloto5.poloto_stroke{stroke:lime;}
-.poloto6.poloto_stroke{stroke:orange;}
-.poloto7.poloto_stroke{stroke:chocolate;}
-.poloto0.poloto_fill{fill:blue;}
-.poloto1.poloto_fill{fill:red;}
-.poloto2.poloto_fill{fill:green;}
-.poloto3.poloto_fill{fill:gold;}
-.poloto4.poloto_fill{fill:aqua;}
-.poloto5.poloto_fill{fill:lime;}
-.poloto6.poloto_fill{fill:orange;}
-.poloto7.poloto_fill{fill:chocolate;}";
+.poloto_imgs.poloto_ticks{stroke-width:3;fill:none;stroke-dasharray:none}
+.poloto_grid{stroke-width:0.5}
+.poloto_text.poloto_colorbar{dominant-baseline:middle;text-anchor:start}
+.poloto_text.poloto_size_legend{dominant-baseline:middle;text-anchor:start}
+.poloto_imgs.poloto_ticks.poloto_size_legend{stroke-width:1}
+.poloto_text.poloto_pie_label{dominant-baseline:middle;text-anchor:middle}
+.poloto_text.poloto_ticks.poloto_r{dominant-baseline:middle;text-anchor:start}
+.poloto_text.poloto_ticks.poloto_theta{dominant-baseline:middle;text-anchor:middle}
+.poloto_candle_up{stroke:green;fill:green}
+.poloto_candle_down{stroke:red;fill:red}
+.poloto_text.poloto_annotation{text-anchor:middle}
+.poloto_imgs.poloto_refline{stroke-width:1.5;stroke-dasharray:6 4;fill-opacity:0.1}
+.poloto_imgs.poloto_refline.poloto_hspan,.poloto_imgs.poloto_refline.poloto_vspan{stroke:none}
+.poloto_imgs.poloto_annotation{stroke-width:1.5;fill:none}
+"
+    };
+}
+
+///
+/// The background, foreground and grid colors of a theme.
+///
+macro_rules! theme_colors {
+    ($bg:literal, $fg:literal, $grid:literal) => {
+        concat!(
+            ".poloto_background{fill:",
+            $bg,
+            ";}\n",
+            ".poloto_text{fill:",
+            $fg,
+            ";}\n",
+            ".poloto_imgs.poloto_ticks{stroke:",
+            $fg,
+            ";}\n",
+            ".poloto_grid{stroke:",
+            $grid,
+            ";}\n",
+            ".poloto_imgs.poloto_refline{stroke:",
+            $fg,
+            ";fill:",
+            $fg,
+            ";}\n",
+            ".poloto_imgs.poloto_annotation{stroke:",
+            $fg,
+            ";}\n",
+        )
+    };
+}
+
+///
+/// The colors of the plots, one per css class.
+///
+macro_rules! palette {
+    ($($i:literal: $c:literal),*) => {
+        concat!(
+            $(".poloto", $i, ".poloto_stroke{stroke:", $c, ";}\n",)*
+            $(".poloto", $i, ".poloto_fill{fill:", $c, ";}\n",)*
+        )
+    };
+}
 
+macro_rules! default_palette {
+    () => {
+        palette!(0: "blue", 1: "red", 2: "green", 3: "gold", 4: "aqua", 5: "lime", 6: "orange", 7: "chocolate")
+    };
+}
+
+impl Theme<'static> {
+    ///
+    /// Default light theme
+    ///
+    pub const fn light() -> Theme<'static> {
         Theme {
-            styles: STYLE_CONFIG_LIGHT_DEFAULT,
+            styles: concat!(
+                shared_rules!(),
+                theme_colors!("AliceBlue", "black", "gray"),
+                default_palette!()
+            ),
         }
     }
+
     pub const fn dark() -> Theme<'static> {
-        const STYLE_CONFIG_DARK_DEFAULT: &str = ".poloto{
-  stroke-linecap:round;
-  stroke-linejoin:round;
-  font-family:Roboto,sans-serif;
-  font-size:16px;
-}
-.poloto_background{fill:#262626;}
-.poloto_scatter{stroke-width:7}
-.poloto_line{stroke-width:2}
-.poloto_text{fill: white;}
-.poloto_name{font-size:24px;dominant-baseline:auto;text-anchor:middle;}
-.poloto_where{dominant-baseline:middle;text-anchor:start}
-.poloto_text.poloto_legend{font-size:20px;dominant-baseline:middle;text-anchor:start;}
-.poloto_text.poloto_ticks.poloto_y{dominant-baseline:middle;text-anchor:end}
-.poloto_text.poloto_ticks.poloto_x{dominant-baseline:auto;text-anchor:middle}
-.poloto_imgs.poloto_ticks{stroke: white;stroke-width:3;fill:none;stroke-dasharray:none}
-.poloto_grid{stroke:gray;stroke-width:0.5}
-
-.poloto0.poloto_stroke{stroke:blue;}
-.poloto1.poloto_stroke{stroke:red;}
-.poloto2.poloto_stroke{stroke:green;}
-.poloto3.poloto_stroke{stroke:gold;}
-.poloto4.poloto_stroke{stroke:aqua;}
-.poloto5.poloto_stroke{stroke:lime;}
-.poloto6.poloto_stroke{stroke:orange;}
-.poloto7.poloto_stroke{stroke:chocolate;}
-.poloto0.poloto_fill{fill:blue;}
-.poloto1.poloto_fill{fill:red;}
-.poloto2.poloto_fill{fill:green;}
-.poloto3.poloto_fill{fill:gold;}
-.poloto4.poloto_fill{fill:aqua;}
-.poloto5.poloto_fill{fill:lime;}
-.poloto6.poloto_fill{fill:orange;}
-.poloto7.poloto_fill{fill:chocolate;}";
         Theme {
-            styles: STYLE_CONFIG_DARK_DEFAULT,
+            styles: concat!(
+                shared_rules!(),
+                theme_colors!("#262626", "white", "gray"),
+                default_palette!()
+            ),
+        }
+    }
+
+    ///
+    /// Uses the light theme, and switches to the dark theme
+    /// if the reader prefers a dark color scheme.
+    ///
+    pub const fn auto() -> Theme<'static> {
+        Theme {
+            styles: concat!(
+                shared_rules!(),
+                theme_colors!("AliceBlue", "black", "gray"),
+                default_palette!(),
+                "@media (prefers-color-scheme: dark) {\n",
+                theme_colors!("#262626", "white", "gray"),
+                "}"
+            ),
+        }
+    }
+
+    ///
+    /// Same as [`Theme::auto`], but every color is read from a css custom property
+    /// if the host page defines it. The supported properties are
+    /// `--poloto-bg`, `--poloto-fg`, `--poloto-grid` and `--poloto-color-0` to `--poloto-color-7`.
+    ///
+    pub const fn auto_css_vars() -> Theme<'static> {
+        Theme {
+            styles: concat!(
+                shared_rules!(),
+                theme_colors!(
+                    "var(--poloto-bg,AliceBlue)",
+                    "var(--poloto-fg,black)",
+                    "var(--poloto-grid,gray)"
+                ),
+                palette!(
+                    0: "var(--poloto-color-0,blue)",
+                    1: "var(--poloto-color-1,red)",
+                    2: "var(--poloto-color-2,green)",
+                    3: "var(--poloto-color-3,gold)",
+                    4: "var(--poloto-color-4,aqua)",
+                    5: "var(--poloto-color-5,lime)",
+                    6: "var(--poloto-color-6,orange)",
+                    7: "var(--poloto-color-7,chocolate)"
+                ),
+                "@media (prefers-color-scheme: dark) {\n",
+                theme_colors!(
+                    "var(--poloto-bg,#262626)",
+                    "var(--poloto-fg,white)",
+                    "var(--poloto-grid,gray)"
+                ),
+                "}"
+            ),
         }
     }
 
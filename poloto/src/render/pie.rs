@@ -0,0 +1,271 @@
+//!
+//! Render pie and donut charts. They have no axes, so only the title is drawn around them.
+//!
+use super::*;
+use crate::build::pie::Pie;
+use std::f64::consts::{FRAC_PI_2, TAU};
+
+///
+/// Link a pie chart with a way to render it.
+///
+pub struct PieFrame<D> {
+    opt: RenderFrame,
+    pie: Pie<D>,
+}
+
+impl<D: Display> PieFrame<D> {
+    pub(super) fn new(opt: RenderFrame, pie: Pie<D>) -> Self {
+        PieFrame { opt, pie }
+    }
+
+    ///
+    /// Only the title is used since there are no axes to name.
+    ///
+    pub fn label<BB: BaseFmt>(self, fmt: BB) -> PieChart<D, BB> {
+        PieChart {
+            data: self,
+            base: fmt,
+        }
+    }
+}
+
+pub struct PieChart<D, BB> {
+    data: PieFrame<D>,
+    base: BB,
+}
+
+impl<D: Display, BB: BaseFmt> PieChart<D, BB> {
+    pub fn append_to<E: Elem>(self, elem: E) -> Stage4<elem::Append<E, Self>> {
+        Stage4(elem.append(self))
+    }
+
+    pub fn headless(self) -> Stage4<Self> {
+        Stage4(self)
+    }
+}
+
+#[cfg(feature = "html")]
+impl<D: Display, BB: BaseFmt> crate::html::Figure for PieChart<D, BB> {
+    fn set_id_prefix(&mut self, prefix: &str) {
+        self.data.opt.id_prefix = prefix.to_string();
+    }
+}
+
+///
+/// Write the outline of a slice going clockwise from `start` to `end`.
+/// Each arc is split in two so that the large arc flag is never needed.
+///
+fn slice_path(
+    w: &mut dyn fmt::Write,
+    ffmt: FloatFmt,
+    [cx, cy]: [f64; 2],
+    [inner, outer]: [f64; 2],
+    [start, end]: [f64; 2],
+) -> fmt::Result {
+    let mid = (start + end) / 2.0;
+    let pos = |r: f64, a: f64| (ffmt.disp(cx + r * a.cos()), ffmt.disp(cy + r * a.sin()));
+
+    let (x, y) = pos(outer, start);
+    write!(w, "M {} {} ", x, y)?;
+    for a in [mid, end] {
+        let (x, y) = pos(outer, a);
+        let r = ffmt.disp(outer);
+        write!(w, "A {} {} 0 0 1 {} {} ", r, r, x, y)?;
+    }
+
+    if inner > 0.0 {
+        let (x, y) = pos(inner, end);
+        write!(w, "L {} {} ", x, y)?;
+        for a in [mid, start] {
+            let (x, y) = pos(inner, a);
+            let r = ffmt.disp(inner);
+            write!(w, "A {} {} 0 0 0 {} {} ", r, r, x, y)?;
+        }
+    } else {
+        write!(w, "L {} {} ", ffmt.disp(cx), ffmt.disp(cy))?;
+    }
+    write!(w, "Z")
+}
+
+impl<D: Display, BB: BaseFmt> ElemOuter for PieChart<D, BB> {
+    fn render<'a>(
+        self,
+        mut writer: ElemStack<'a, Sentinel>,
+    ) -> Result<ElemStack<'a, Sentinel>, fmt::Error> {
+        let PieChart { data, base } = self;
+        let PieFrame { opt, pie } = data;
+
+        let RenderFrame {
+            width,
+            height,
+            padding,
+            paddingy,
+            yaspect_offset,
+            spacing,
+            legendx1,
+            num_css_classes,
+            precision,
+            ..
+        } = opt;
+
+        let ffmt = FloatFmt::new(precision);
+        let id_prefix = &opt.id_prefix;
+
+        let mut title = String::new();
+        base.write_title(&mut title)?;
+        writer.put(
+            hbuild::elem("title")
+                .with(("id", format_move!("{}title", id_prefix)))
+                .append(hbuild::raw(&title))
+                .inline(),
+        )?;
+
+        writer.put(hbuild::single("circle").with(attrs!(
+            ("r", "1e5"),
+            ("class", "poloto_background"),
+            ("fill", "white")
+        )))?;
+
+        let center = [width / 2.0, height / 2.0];
+        let outer = ((width - padding * 2.0).min(height - paddingy * 2.0) / 2.0).max(0.0);
+        let inner = outer * pie.inner_radius();
+
+        let colori = |i: usize| num_css_classes.map(|n| i % n).unwrap_or(i);
+
+        let mut slices = vec![];
+        let mut labels = vec![];
+        let mut desc = vec![];
+        let mut start = -FRAC_PI_2;
+        for (i, (name, value, frac)) in pie.fractions().enumerate() {
+            let name = name.to_string();
+            let percent = frac * 100.0;
+            if !name.is_empty() {
+                desc.push(format!("{} {:.1}%", name, percent));
+            }
+            if frac <= 0.0 {
+                continue;
+            }
+
+            let end = start + frac * TAU;
+
+            let mut d = String::new();
+            slice_path(&mut d, ffmt, center, [inner, outer], [start, end])?;
+            let tooltip = format!("{}: {} ({:.1}%)", name, value, percent);
+            slices.push((colori(i), d, tooltip));
+
+            let mid = (start + end) / 2.0;
+            let r = if inner > 0.0 {
+                (inner + outer) / 2.0
+            } else {
+                outer * 0.65
+            };
+            labels.push((
+                center[0] + r * mid.cos(),
+                center[1] + r * mid.sin(),
+                percent,
+            ));
+
+            start = end;
+        }
+
+        writer.put(
+            hbuild::elem("g")
+                .with(("class", "poloto_plot poloto_imgs poloto_pie"))
+                .append(hbuild::from_iter(slices.into_iter().map(
+                    |(c, d, tooltip)| {
+                        hbuild::elem("path")
+                            .with(attrs!(
+                                ("class", format_move!("poloto{} poloto_fill", c)),
+                                ("d", d)
+                            ))
+                            .append(hbuild::elem("title").append(hbuild::raw(tooltip)).inline())
+                    },
+                ))),
+        )?;
+
+        if pie.percentages() {
+            writer.put(
+                hbuild::elem("text")
+                    .with(("class", "poloto_text poloto_pie_label"))
+                    .append(hbuild::from_iter(labels.into_iter().map(
+                        |(x, y, percent)| {
+                            hbuild::elem("tspan")
+                                .with(attrs!(("x", ffmt.disp(x)), ("y", ffmt.disp(y))))
+                                .append(hbuild::raw(format_move!("{:.1}%", percent)))
+                                .inline()
+                        },
+                    ))),
+            )?;
+        }
+
+        for (i, (name, _, _)) in pie.fractions().enumerate() {
+            let name = name.to_string();
+            if name.is_empty() {
+                continue;
+            }
+            let legendy1 = paddingy - yaspect_offset - padding / 8.0 + (i as f64) * spacing;
+
+            writer.put(
+                hbuild::elem("g")
+                    .with((
+                        "class",
+                        format_move!(
+                            "poloto_legend poloto_imgs poloto_pie poloto{} poloto_fill",
+                            colori(i)
+                        ),
+                    ))
+                    .append(hbuild::single("rect").with(attrs!(
+                        ("x", legendx1),
+                        ("y", legendy1 - padding / 30.0),
+                        ("width", padding / 3.0),
+                        ("height", padding / 20.0),
+                        ("rx", padding / 30.0),
+                        ("ry", padding / 30.0)
+                    )))
+                    .inline(),
+            )?;
+
+            writer.put(
+                hbuild::elem("text")
+                    .with(attrs!(
+                        (
+                            "class",
+                            format_move!(
+                                "poloto_legend poloto_text poloto_pie poloto{}",
+                                colori(i)
+                            )
+                        ),
+                        ("x", width - padding / 1.2),
+                        ("y", paddingy - yaspect_offset + (i as f64) * spacing)
+                    ))
+                    .append(hbuild::raw(name))
+                    .inline(),
+            )?;
+        }
+
+        writer.put(
+            hbuild::elem("text")
+                .with(attrs!(
+                    ("class", "poloto_text poloto_name poloto_title"),
+                    ("x", ffmt.disp(width / 2.0)),
+                    ("y", ffmt.disp(padding / 4.0))
+                ))
+                .append(hbuild::raw(title))
+                .inline(),
+        )?;
+
+        let mut summary = String::from("Pie chart.");
+        if !desc.is_empty() {
+            use std::fmt::Write;
+            write!(summary, " Slices: {}.", desc.join(", "))?;
+        }
+        writer.put(
+            hbuild::elem("desc")
+                .with(("id", format_move!("{}desc", id_prefix)))
+                .append(hbuild::raw(summary))
+                .inline(),
+        )?;
+
+        Ok(writer)
+    }
+}
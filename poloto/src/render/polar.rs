@@ -0,0 +1,453 @@
+//!
+//! Render plots on polar axes, where each point is `(theta, r)`.
+//!
+//! Line, scatter, marker and fill plots are supported. Any other plot type panics.
+//! Fills are closed on themselves instead of dropping down to the x axis.
+//! Smooth plots are sampled along the curve in `(theta, r)`, so that they bend around the center.
+//!
+use super::*;
+use crate::build::polar::Radar;
+use crate::build::{PlotMetaType, PlotTag, PlotType};
+use std::f64::consts::PI;
+
+///
+/// Link some plots with polar axes.
+///
+pub struct PolarFrame<P: PlotIterator> {
+    opt: RenderFrame,
+    plots: P,
+    rmax: f64,
+    spokes: Option<Vec<(f64, String)>>,
+    clockwise: bool,
+    degrees: bool,
+}
+
+impl<L: Point<X = f64, Y = f64>, P: PlotIterator<L = L>> PolarFrame<P> {
+    pub(super) fn new(opt: RenderFrame, plots: P) -> PolarFrame<PlotRes<P::P, L>> {
+        let PlotRes {
+            area,
+            it,
+            num_plots,
+        } = plots.unpack();
+
+        let (_, boundr) = area.build();
+        let rmax = boundr.max.abs().max(boundr.min.abs());
+
+        PolarFrame {
+            opt,
+            plots: PlotRes {
+                area,
+                it,
+                num_plots,
+            },
+            rmax,
+            spokes: None,
+            clockwise: false,
+            degrees: false,
+        }
+    }
+
+    ///
+    /// Read theta as degrees instead of radians. This also applies to [`PolarFrame::with_spokes`].
+    ///
+    pub fn with_degrees(mut self) -> Self {
+        self.degrees = true;
+        self
+    }
+
+    ///
+    /// Measure theta clockwise from the top, like a compass,
+    /// instead of counter clockwise from the right.
+    ///
+    pub fn clockwise_from_top(mut self) -> Self {
+        self.clockwise = true;
+        self
+    }
+
+    ///
+    /// Draw spokes at these angles with these labels instead of every 30 degrees.
+    ///
+    pub fn with_spokes<D: Display>(mut self, spokes: impl IntoIterator<Item = (f64, D)>) -> Self {
+        self.spokes = Some(
+            spokes
+                .into_iter()
+                .map(|(a, d)| (a, d.to_string()))
+                .collect(),
+        );
+        self
+    }
+
+    ///
+    /// Draw one spoke per category of the radar chart, starting at the top and going clockwise.
+    /// The angles of the radar have to be in the same unit as the frame, see [`Radar::with_degrees`].
+    ///
+    pub fn with_radar<D: Display>(self, radar: &Radar<D>) -> Self {
+        self.with_spokes(radar.spokes()).clockwise_from_top()
+    }
+
+    pub fn label<BB: BaseFmt>(self, fmt: BB) -> PolarChart<P, BB> {
+        PolarChart {
+            data: self,
+            base: fmt,
+        }
+    }
+}
+
+pub struct PolarChart<P: PlotIterator, BB> {
+    data: PolarFrame<P>,
+    base: BB,
+}
+
+impl<L: Point<X = f64, Y = f64>, P: PlotIterator<L = L>, BB: BaseFmt> PolarChart<P, BB> {
+    pub fn append_to<E: Elem>(self, elem: E) -> Stage4<elem::Append<E, Self>> {
+        Stage4(elem.append(self))
+    }
+
+    pub fn headless(self) -> Stage4<Self> {
+        Stage4(self)
+    }
+}
+
+#[cfg(feature = "html")]
+impl<L: Point<X = f64, Y = f64>, P: PlotIterator<L = L>, BB: BaseFmt> crate::html::Figure
+    for PolarChart<P, BB>
+{
+    fn set_id_prefix(&mut self, prefix: &str) {
+        self.data.opt.id_prefix = prefix.to_string();
+    }
+}
+
+///
+/// The number of lines each segment of a smooth plot is drawn with.
+///
+const SMOOTH_SAMPLES: usize = 16;
+
+///
+/// Fills can't drop down to an axis, so close them on themselves.
+/// Smooth curves are drawn as lines sampled from the curve.
+///
+fn polar_type(typ: PlotType) -> PlotType {
+    match typ {
+        PlotType::LineFill | PlotType::LineFillSmooth => PlotType::LineFillRaw,
+        PlotType::LineSmooth => PlotType::Line,
+        PlotType::Step(_)
+        | PlotType::Histo
+        | PlotType::Bars
+        | PlotType::Heatmap(_)
+        | PlotType::Candle
+        | PlotType::Quiver(_)
+        | PlotType::Bubble(_) => {
+            panic!("only line, scatter and fill plots can be drawn on polar axes")
+        }
+        typ => typ,
+    }
+}
+
+///
+/// Sample the monotone curve through the points, the same curve that is drawn on cartesian axes.
+///
+fn sample_smooth(run: &[[f64; 2]], out: &mut Vec<[f64; 2]>) {
+    let tangents = render_plot::monotone_tangents(run);
+    out.extend(run.first());
+    for (k, pair) in run.windows(2).enumerate() {
+        let ([x0, y0], [x1, y1]) = (pair[0], pair[1]);
+        let h = x1 - x0;
+        let (m0, m1) = (tangents[k] * h, tangents[k + 1] * h);
+        out.extend((1..=SMOOTH_SAMPLES).map(|i| {
+            let t = i as f64 / SMOOTH_SAMPLES as f64;
+            let (t2, t3) = (t * t, t * t * t);
+            let y = (2.0 * t3 - 3.0 * t2 + 1.0) * y0
+                + (t3 - 2.0 * t2 + t) * m0
+                + (-2.0 * t3 + 3.0 * t2) * y1
+                + (t3 - t2) * m1;
+            [x0 + t * h, y]
+        }));
+    }
+}
+
+///
+/// Make the plots drawable on polar axes. See [`polar_type`].
+///
+fn polar_tags<L: Point<X = f64, Y = f64>, D: Display>(
+    it: impl Iterator<Item = PlotTag<L, D>>,
+) -> Vec<PlotTag<(f64, f64), D>> {
+    let mut out = vec![];
+    let mut smooth = None;
+
+    for tag in it {
+        match tag {
+            PlotTag::Start {
+                name,
+                typ,
+                size_hint,
+            } => {
+                let typ = if let PlotMetaType::Plot(typ) = typ {
+                    if matches!(typ, PlotType::LineSmooth | PlotType::LineFillSmooth) {
+                        smooth = Some(vec![]);
+                    }
+                    PlotMetaType::Plot(polar_type(typ))
+                } else {
+                    typ
+                };
+                out.push(PlotTag::Start {
+                    name,
+                    typ,
+                    size_hint,
+                });
+            }
+            PlotTag::Plot(l) => {
+                let (&x, &y) = l.get();
+                match &mut smooth {
+                    Some(points) => points.push([x, y]),
+                    None => out.push(PlotTag::Plot((x, y))),
+                }
+            }
+            PlotTag::Finish() => {
+                if let Some(points) = smooth.take() {
+                    let mut sampled = vec![];
+                    //Holes split the curve.
+                    for (i, run) in points
+                        .split(|[x, y]| !(x.is_finite() && y.is_finite()))
+                        .enumerate()
+                    {
+                        if i != 0 {
+                            sampled.push([f64::NAN, f64::NAN]);
+                        }
+                        sample_smooth(run, &mut sampled);
+                    }
+
+                    if let Some(PlotTag::Start { size_hint, .. }) = out.last_mut() {
+                        let n = sampled.len() + 2;
+                        *size_hint = (n, Some(n));
+                    }
+                    out.extend(sampled.into_iter().map(|[x, y]| PlotTag::Plot((x, y))));
+                }
+                out.push(PlotTag::Finish());
+            }
+        }
+    }
+    out
+}
+
+impl<L: Point<X = f64, Y = f64>, P: PlotIterator<L = L>, BB: BaseFmt> ElemOuter
+    for PolarChart<P, BB>
+{
+    fn render<'a>(
+        self,
+        mut writer: ElemStack<'a, Sentinel>,
+    ) -> Result<ElemStack<'a, Sentinel>, fmt::Error> {
+        let PolarChart { data, base } = self;
+        let PolarFrame {
+            mut opt,
+            plots,
+            rmax,
+            spokes,
+            clockwise,
+            degrees,
+        } = data;
+
+        let RenderFrame {
+            width,
+            height,
+            padding,
+            paddingy,
+            precision,
+            ..
+        } = opt;
+
+        let ffmt = FloatFmt::new(precision);
+        let id_prefix = &opt.id_prefix;
+
+        let mut title = String::new();
+        base.write_title(&mut title)?;
+        writer.put(
+            hbuild::elem("title")
+                .with(("id", format_move!("{}title", id_prefix)))
+                .append(hbuild::raw(&title))
+                .inline(),
+        )?;
+
+        writer.put(hbuild::single("circle").with(attrs!(
+            ("r", "1e5"),
+            ("class", "poloto_background"),
+            ("fill", "white")
+        )))?;
+
+        let [cx, cy] = [width / 2.0, height / 2.0];
+        let radius = ((width - padding * 2.0).min(height - paddingy * 2.0) / 2.0).max(0.0);
+        let to_radians = if degrees { PI / 180.0 } else { 1.0 };
+
+        //The direction on screen of an angle in radians.
+        let dir = move |a: f64| {
+            if clockwise {
+                [a.sin(), -a.cos()]
+            } else {
+                [a.cos(), -a.sin()]
+            }
+        };
+
+        let spokes = spokes.map_or_else(
+            || {
+                (0..12)
+                    .map(|k| (k as f64 * PI / 6.0, format!("{}°", k * 30)))
+                    .collect()
+            },
+            |s| {
+                s.into_iter()
+                    .map(|(a, d)| (a * to_radians, d))
+                    .collect::<Vec<_>>()
+            },
+        );
+
+        let mut counter = 0;
+        let ticks = f64::default_ticks()
+            .generate(
+                &DataBound {
+                    min: 0.0,
+                    max: rmax,
+                },
+                &RenderFrameBound {
+                    ideal_num_steps: ((radius / 40.0).floor() as u32).max(2),
+                    ideal_dash_size: 20.0,
+                    max: radius,
+                    axis: Axis::Y,
+                },
+                IndexRequester::new(&mut counter),
+            )
+            .unwrap();
+
+        use crate::ticks::tick_fmt::TickFmt;
+        let mut rings = vec![];
+        for val in ticks.iter.into_iter().filter(|v| *v > 0.0 && *v <= rmax) {
+            let mut s = String::new();
+            ticks.fmt.write_tick(&mut s, &val)?;
+            rings.push((val / rmax * radius, s));
+        }
+        let mut where_fmt = String::new();
+        ticks.fmt.write_where(&mut where_fmt)?;
+
+        let spoke_lines = spokes.iter().map(|(a, _)| dir(*a)).collect::<Vec<_>>();
+        writer.put(
+            hbuild::elem("g")
+                .with(attrs!(
+                    ("class", "poloto_grid poloto_polar"),
+                    ("fill", "none")
+                ))
+                .append(
+                    hbuild::single("path").with(hbuild::path_from_closure(move |w| {
+                        let mut w = w.start();
+                        use tagu::attr::PathCommand::*;
+                        for [dx, dy] in spoke_lines {
+                            w.put(M(ffmt.disp(cx), ffmt.disp(cy)))?;
+                            w.put(L(ffmt.disp(cx + dx * radius), ffmt.disp(cy + dy * radius)))?;
+                        }
+                        Ok(())
+                    })),
+                )
+                .append(hbuild::from_iter(rings.iter().map(|(r, _)| {
+                    hbuild::single("circle").with(attrs!(
+                        ("cx", ffmt.disp(cx)),
+                        ("cy", ffmt.disp(cy)),
+                        ("r", ffmt.disp(*r))
+                    ))
+                }))),
+        )?;
+
+        let project = move |theta: &f64, r: &f64| {
+            let [dx, dy] = dir(theta * to_radians);
+            let r = r / rmax * radius;
+            [cx + dx * r, cy + dy * r]
+        };
+
+        let PlotRes {
+            area,
+            it,
+            num_plots,
+        } = plots.unpack();
+        let plots = PlotRes {
+            area,
+            it: polar_tags(it).into_iter(),
+            num_plots,
+        };
+
+        //Points aren't sorted by x on polar axes.
+        opt.downsample = None;
+        let (mut writer, plot_names) =
+            render_plot::render_plot_with(writer, &opt, plots, None, project)?;
+
+        writer.put(hbuild::single("circle").with(attrs!(
+            ("class", "poloto_imgs poloto_ticks poloto_polar"),
+            ("cx", ffmt.disp(cx)),
+            ("cy", ffmt.disp(cy)),
+            ("r", ffmt.disp(radius))
+        )))?;
+
+        writer.put(
+            hbuild::elem("text")
+                .with(("class", "poloto_text poloto_ticks poloto_r"))
+                .append(hbuild::from_iter(rings.into_iter().map(|(r, s)| {
+                    hbuild::elem("tspan")
+                        .with(attrs!(
+                            ("x", ffmt.disp(cx + padding / 30.0)),
+                            ("y", ffmt.disp(cy - r))
+                        ))
+                        .append(hbuild::raw(s))
+                        .inline()
+                }))),
+        )?;
+
+        let label_radius = radius + padding / 6.0;
+        writer.put(
+            hbuild::elem("text")
+                .with(("class", "poloto_text poloto_ticks poloto_theta"))
+                .append(hbuild::from_iter(spokes.into_iter().map(|(a, s)| {
+                    let [dx, dy] = dir(a);
+                    hbuild::elem("tspan")
+                        .with(attrs!(
+                            ("x", ffmt.disp(cx + dx * label_radius)),
+                            ("y", ffmt.disp(cy + dy * label_radius))
+                        ))
+                        .append(hbuild::raw(s))
+                        .inline()
+                }))),
+        )?;
+
+        if !where_fmt.is_empty() {
+            writer.put(
+                hbuild::elem("text")
+                    .with(attrs!(
+                        ("class", "poloto_text poloto_where poloto_r"),
+                        ("x", ffmt.disp(padding)),
+                        ("y", ffmt.disp(paddingy * 0.7))
+                    ))
+                    .append(hbuild::raw(where_fmt))
+                    .inline(),
+            )?;
+        }
+
+        writer.put(
+            hbuild::elem("text")
+                .with(attrs!(
+                    ("class", "poloto_text poloto_name poloto_title"),
+                    ("x", ffmt.disp(width / 2.0)),
+                    ("y", ffmt.disp(padding / 4.0))
+                ))
+                .append(hbuild::raw(title))
+                .inline(),
+        )?;
+
+        let mut desc = format!("Polar plot with r from 0 to {:?}.", rmax);
+        if !plot_names.is_empty() {
+            use std::fmt::Write;
+            write!(desc, " Plots: {}.", plot_names.join(", "))?;
+        }
+        writer.put(
+            hbuild::elem("desc")
+                .with(("id", format_move!("{}desc", id_prefix)))
+                .append(hbuild::raw(desc))
+                .inline(),
+        )?;
+
+        Ok(writer)
+    }
+}
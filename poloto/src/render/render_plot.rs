@@ -73,32 +73,67 @@ pub(super) fn render_plot<
     L: Point<X = X, Y = Y>,
     P: build::PlotIterator<L = L>,
 >(
-    mut writer: ElemStack<'a, Sentinel>,
+    writer: ElemStack<'a, Sentinel>,
     boundx: &ticks::DataBound<X>,
     boundy: &ticks::DataBound<Y>,
     canvas: &RenderFrame,
     plots_all: P,
-) -> Result<ElemStack<'a, Sentinel>, fmt::Error> {
+    recorder: Option<&mut export::Recorder<X, Y>>,
+) -> Result<(ElemStack<'a, Sentinel>, Vec<String>), fmt::Error> {
     let RenderFrame {
-        width,
         height,
         padding,
         paddingy,
         xaspect_offset,
         yaspect_offset,
-        spacing,
-        num_css_classes,
         ..
-    } = canvas;
+    } = *canvas;
 
     let scalex = canvas.boundx.max;
     let scaley = canvas.boundy.max;
 
-    let boundx = [boundx.min, boundx.max];
-    let boundy = [boundy.min, boundy.max];
+    let rangex = [boundx.min, boundx.max];
+    let rangey = [boundy.min, boundy.max];
+
+    let basex = xaspect_offset + padding - boundx.min.scale(&rangex, scalex);
+    let basey = yaspect_offset + height - paddingy + boundy.min.scale(&rangey, scaley);
 
-    let [minx, maxx] = boundx;
-    let [miny, maxy] = boundy;
+    let project = move |x: &X, y: &Y| {
+        [
+            basex + x.scale(&rangex, scalex),
+            basey - y.scale(&rangey, scaley),
+        ]
+    };
+
+    render_plot_with(writer, canvas, plots_all, recorder, project)
+}
+
+///
+/// Render the plots and their legend, using `project` to find where each point goes.
+///
+pub(super) fn render_plot_with<
+    'a,
+    X: PlotNum,
+    Y: PlotNum,
+    L: Point<X = X, Y = Y>,
+    P: build::PlotIterator<L = L>,
+>(
+    mut writer: ElemStack<'a, Sentinel>,
+    canvas: &RenderFrame,
+    plots_all: P,
+    mut recorder: Option<&mut export::Recorder<X, Y>>,
+    project: impl Fn(&X, &Y) -> [f64; 2],
+) -> Result<(ElemStack<'a, Sentinel>, Vec<String>), fmt::Error> {
+    let RenderFrame {
+        width,
+        height,
+        padding,
+        paddingy,
+        yaspect_offset,
+        spacing,
+        num_css_classes,
+        ..
+    } = canvas;
 
     let mut color_iter = {
         let max = if let Some(nn) = *num_css_classes {
@@ -118,60 +153,100 @@ pub(super) fn render_plot<
         mut it, num_plots, ..
     } = plots_all.unpack();
 
-    for i in 0..num_plots {
+    //Annotations and reference lines don't take up a spot in the legend.
+    let mut num_slots = 0;
+
+    for _ in 0..num_plots {
         let (mut it, label, typ) = SinglePlotIterator::new(&mut it).unwrap();
 
         let mut name = String::new();
         use std::fmt::Write;
         write!(&mut name, "{}", label).unwrap();
 
-        let name_exists = !name.is_empty();
-
-        if name_exists {
-            names.push((typ, name, i));
+        //Reference lines only have one meaningful coordinate, so they are not recorded.
+        if let Some(r) = recorder.as_deref_mut() {
+            if !matches!(typ, PlotMetaType::RefLine(_)) {
+                r.start(name.clone(), typ);
+            }
         }
 
-        let aa = minx.scale(&[minx, maxx], scalex);
-        let bb = miny.scale(&[miny, maxy], scaley);
-
         match typ {
             PlotMetaType::Text => {
                 assert_eq!((&mut it).count(), 0);
 
                 // don't need to render any legend or plots
             }
+            PlotMetaType::Annotation(style) => {
+                for l in it {
+                    if let Some(r) = recorder.as_deref_mut() {
+                        r.push(&l);
+                    }
+                    let (x, y) = l.get();
+                    render_annotation(&mut writer, canvas, &name, project(x, y), style)?;
+                }
+                continue;
+            }
+            PlotMetaType::RefLine(kind) => {
+                let pos: Vec<_> = it
+                    .map(|l| {
+                        let (x, y) = l.get();
+                        project(x, y)
+                    })
+                    .collect();
+                render_refline(&mut writer, canvas, &name, &pos, kind)?;
+                continue;
+            }
             PlotMetaType::Plot(p_type) => {
                 let colori = color_iter.next().unwrap();
 
-                let rangex_ii = &[minx, maxx];
-                let rangey_ii = &[miny, maxy];
-                let basex_ii = xaspect_offset + padding - aa;
-                let basey_ii = yaspect_offset + height - paddingy + bb;
-                let maxx_ii = scalex;
-                let maxy_ii = scaley;
+                let it = it.inspect(|l| {
+                    if let Some(r) = recorder.as_deref_mut() {
+                        r.push(l);
+                    }
+                });
 
+                let project = &project;
                 let it = it.map(move |l| {
                     let (x, y) = l.get();
-                    [
-                        basex_ii + x.scale(rangex_ii, maxx_ii),
-                        basey_ii - y.scale(rangey_ii, maxy_ii),
-                    ]
+                    (project(x, y), l.value(), l.color(), l.vector())
                 });
 
                 let precision = canvas.precision;
-                render(
-                    &mut writer,
-                    it,
-                    PlotRenderInfo {
-                        canvas,
-                        p_type,
-                        colori,
-                        precision,
-                        bar_width: canvas.bar_width,
-                    },
-                )?;
+                let info = PlotRenderInfo {
+                    canvas,
+                    p_type,
+                    colori,
+                    index: num_slots,
+                    precision,
+                    bar_width: canvas.bar_width,
+                };
+                match p_type {
+                    PlotType::Heatmap(style) => {
+                        render_heatmap(&mut writer, it.map(|(p, v, _, _)| (p, v)), style, info)?
+                    }
+                    PlotType::Bubble(style) => {
+                        render_bubble(&mut writer, it.map(|(p, v, c, _)| (p, v, c)), style, info)?
+                    }
+                    PlotType::Candle => {
+                        render_candles(&mut writer, it.map(|(p, _, _, _)| p), info)?
+                    }
+                    PlotType::Quiver(style) => {
+                        render_quiver(&mut writer, it.map(|(p, _, _, v)| (p, v)), style, info)?
+                    }
+                    _ => {
+                        let it =
+                            downsample::apply(canvas.downsample, p_type, it.map(|(p, _, _, _)| p));
+                        let it = simplify::apply(canvas.simplify, canvas.precision, p_type, it);
+                        render(&mut writer, it, info)?
+                    }
+                }
             }
         }
+
+        if !name.is_empty() {
+            names.push((typ, name, num_slots));
+        }
+        num_slots += 1;
     }
 
     assert!(SinglePlotIterator::new(&mut it).is_none());
@@ -179,7 +254,7 @@ pub(super) fn render_plot<
     if !names.is_empty() {
         for (typ, name, i) in names.iter() {
             match typ {
-                PlotMetaType::Text => {
+                PlotMetaType::Text | PlotMetaType::Annotation(_) | PlotMetaType::RefLine(_) => {
                     // don't need to render any legend or plots
                 }
                 &PlotMetaType::Plot(p_type) => {
@@ -194,6 +269,7 @@ pub(super) fn render_plot<
                                 canvas,
                                 p_type,
                                 colori,
+                                index: *i,
                                 legendy1,
                             },
                         )?;
@@ -209,10 +285,19 @@ pub(super) fn render_plot<
                     PlotType::Line => "poloto_line",
                     PlotType::Histo => "poloto_histo",
                     PlotType::LineFill => "poloto_linefill",
+                    PlotType::LineSmooth => "poloto_line poloto_smooth",
+                    PlotType::LineFillSmooth => "poloto_linefill poloto_smooth",
                     PlotType::LineFillRaw => "poloto_linefillraw",
                     PlotType::Bars => "poloto_bars",
+                    PlotType::Heatmap(_) => "poloto_heatmap",
+                    PlotType::Bubble(_) => "poloto_bubble",
+                    PlotType::Candle => "poloto_candle",
+                    PlotType::Quiver(_) => "poloto_quiver",
+                    PlotType::Marker(_) => "poloto_marker",
+                    PlotType::Step(_) => "poloto_line poloto_step",
+                    PlotType::LineMarker(_) => "poloto_line poloto_marker",
                 },
-                PlotMetaType::Text => "",
+                PlotMetaType::Text | PlotMetaType::Annotation(_) | PlotMetaType::RefLine(_) => "",
             };
 
             let text = hbuild::elem("text")
@@ -231,13 +316,51 @@ pub(super) fn render_plot<
 
         writer.put(bb)?;
     }
-    Ok(writer)
+
+    //Size legends and then colorbars go below the legend.
+    let mut extra_top = paddingy - yaspect_offset + (num_slots as f64) * spacing;
+    let extra_bottom = yaspect_offset + height - paddingy;
+    for (typ, _, _) in names.iter() {
+        if let PlotMetaType::Plot(PlotType::Bubble(style)) = typ {
+            extra_top = render_size_legend(&mut writer, canvas, *style, extra_top)?;
+        }
+    }
+    for (typ, _, _) in names.iter() {
+        match typ {
+            PlotMetaType::Plot(PlotType::Heatmap(style)) => {
+                let range = [extra_top, extra_bottom];
+                render_colorbar(&mut writer, canvas, style.colormap, style.range, range)?;
+            }
+            PlotMetaType::Plot(PlotType::Bubble(style)) => {
+                if let Some(colormap) = style.colormap {
+                    let range = [extra_top, extra_bottom];
+                    render_colorbar(&mut writer, canvas, colormap, style.color_range, range)?;
+                }
+            }
+            PlotMetaType::Plot(PlotType::Quiver(style)) => {
+                if let Some(colormap) = style.colormap {
+                    let range = [extra_top, extra_bottom];
+                    render_colorbar(&mut writer, canvas, colormap, style.range, range)?;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let plot_names = names
+        .into_iter()
+        .filter(|(typ, _, _)| matches!(typ, PlotMetaType::Plot(_)))
+        .map(|(_, name, _)| name)
+        .collect();
+
+    Ok((writer, plot_names))
 }
 
 struct PlotRenderInfo2<'a> {
     canvas: &'a RenderFrame,
     p_type: PlotType,
     colori: usize,
+    index: usize,
     legendy1: f64,
 }
 
@@ -245,6 +368,7 @@ struct PlotRenderInfo<'a> {
     canvas: &'a RenderFrame,
     p_type: PlotType,
     colori: usize,
+    index: usize,
     precision: usize,
     bar_width: f64,
 }
@@ -254,6 +378,7 @@ fn render_label<T>(writer: &mut ElemStack<T>, info: PlotRenderInfo2) -> fmt::Res
         canvas,
         p_type,
         colori,
+        index,
         legendy1,
         ..
     } = info;
@@ -263,7 +388,7 @@ fn render_label<T>(writer: &mut ElemStack<T>, info: PlotRenderInfo2) -> fmt::Res
     } = *canvas;
 
     match p_type {
-        PlotType::Line => {
+        PlotType::Line | PlotType::LineSmooth | PlotType::Step(_) => {
             let g = hbuild::elem("g").with((
                 "class",
                 format_move!(
@@ -317,7 +442,7 @@ fn render_label<T>(writer: &mut ElemStack<T>, info: PlotRenderInfo2) -> fmt::Res
 
             writer.put(g.inline())?;
         }
-        PlotType::LineFill => {
+        PlotType::LineFill | PlotType::LineFillSmooth => {
             let g = hbuild::elem("g").with((
                 "class",
                 format_move!(
@@ -359,6 +484,155 @@ fn render_label<T>(writer: &mut ElemStack<T>, info: PlotRenderInfo2) -> fmt::Res
             writer.put(g.inline())?;
         }
 
+        PlotType::Marker(marker) => {
+            let g = hbuild::elem("g").with((
+                "class",
+                format_move!(
+                    "poloto_legend poloto_imgs poloto_marker poloto{} poloto_fill poloto_stroke",
+                    colori
+                ),
+            ));
+            let center = [legendx1 + padding / 6.0, legendy1];
+            let g = g.append(hbuild::single("path").with(attrs!(marker_path(
+                std::iter::once(center),
+                marker,
+                padding / 30.0,
+                FloatFmt::new(canvas.precision)
+            ))));
+            writer.put(g.inline())?;
+        }
+        PlotType::LineMarker(marker) => {
+            let g = hbuild::elem("g").with((
+                "class",
+                format_move!("poloto_legend poloto_imgs poloto_line poloto{}", colori),
+            ));
+            let line = hbuild::single("line").with(attrs!(
+                ("class", format_move!("poloto{} poloto_stroke", colori)),
+                ("x1", legendx1),
+                ("x2", legendx1 + padding / 3.0),
+                ("y1", legendy1),
+                ("y2", legendy1)
+            ));
+            let center = [legendx1 + padding / 6.0, legendy1];
+            let shape = hbuild::single("path").with(attrs!(
+                (
+                    "class",
+                    format_move!("poloto_marker poloto{} poloto_fill poloto_stroke", colori)
+                ),
+                marker_path(
+                    std::iter::once(center),
+                    marker,
+                    padding / 30.0,
+                    FloatFmt::new(canvas.precision)
+                )
+            ));
+            writer.put(g.append(line.chain(shape)).inline())?;
+        }
+
+        PlotType::Quiver(style) => {
+            let g = hbuild::elem("g").with((
+                "class",
+                format_move!(
+                    "poloto_legend poloto_imgs poloto_quiver poloto{} poloto_stroke",
+                    colori
+                ),
+            ));
+            let stroke = style
+                .colormap
+                .map(|c| c.color(0.5))
+                .map(|[r, g, b]| ("stroke", format_move!("rgb({},{},{})", r, g, b)));
+            //Colored arrows have a marker just for the legend.
+            let suffix = if style.colormap.is_some() {
+                "_legend"
+            } else {
+                ""
+            };
+            let line = hbuild::single("line").with(attrs!(
+                ("x1", legendx1),
+                ("x2", legendx1 + padding / 3.0),
+                ("y1", legendy1),
+                ("y2", legendy1),
+                (
+                    "marker-end",
+                    format_move!("url(#{}arrow{}{})", canvas.id_prefix, index, suffix)
+                ),
+                stroke
+            ));
+            writer.put(g.append(line).inline())?;
+        }
+
+        PlotType::Candle => {
+            let g = hbuild::elem("g").with((
+                "class",
+                format_move!(
+                    "poloto_legend poloto_imgs poloto_candle poloto{} poloto_candle_up",
+                    colori
+                ),
+            ));
+            let x = legendx1 + padding / 6.0;
+            let wick = hbuild::single("line").with(attrs!(
+                ("x1", x),
+                ("x2", x),
+                ("y1", legendy1 - padding / 15.0),
+                ("y2", legendy1 + padding / 15.0)
+            ));
+            let body = hbuild::single("rect").with(attrs!(
+                ("x", x - padding / 30.0),
+                ("y", legendy1 - padding / 30.0),
+                ("width", padding / 15.0),
+                ("height", padding / 15.0)
+            ));
+            writer.put(g.append(wick.chain(body)).inline())?;
+        }
+
+        PlotType::Bubble(style) => {
+            let class = if style.colormap.is_some() {
+                ""
+            } else {
+                "poloto_fill"
+            };
+            let g = hbuild::elem("g").with((
+                "class",
+                format_move!(
+                    "poloto_legend poloto_imgs poloto_bubble poloto{} {}",
+                    colori,
+                    class
+                ),
+            ));
+            let fill = style
+                .colormap
+                .map(|c| c.color(0.5))
+                .map(|[r, g, b]| ("fill", format_move!("rgb({},{},{})", r, g, b)));
+
+            let g = g.append(hbuild::single("circle").with(attrs!(
+                ("cx", legendx1 + padding / 6.0),
+                ("cy", legendy1),
+                ("r", padding / 20.0),
+                fill
+            )));
+            writer.put(g.inline())?;
+        }
+
+        PlotType::Heatmap(style) => {
+            let g = hbuild::elem("g").with((
+                "class",
+                format_move!("poloto_legend poloto_imgs poloto_heatmap poloto{}", colori),
+            ));
+
+            let g = g.append(hbuild::from_iter((0..3).map(move |i| {
+                let [r, gr, b] = style.colormap.color(i as f64 / 2.0);
+                hbuild::single("rect").with(attrs!(
+                    ("x", legendx1 + (i as f64) * padding / 9.0),
+                    ("y", legendy1 - padding / 30.0),
+                    ("width", padding / 9.0),
+                    ("height", padding / 20.0),
+                    ("fill", format_move!("rgb({},{},{})", r, gr, b))
+                ))
+            })));
+
+            writer.put(g.inline())?;
+        }
+
         PlotType::Bars => {
             let g = hbuild::elem("g").with((
                 "class",
@@ -409,7 +683,7 @@ fn render<T>(
     match p_type {
         PlotType::Line => {
             let g = hbuild::elem("g").with(attrs!(
-                ("id", format_move!("poloto_plot{}", colori)),
+                ("id", format_move!("{}plot{}", canvas.id_prefix, colori)),
                 (
                     "class",
                     format_move!(
@@ -421,12 +695,14 @@ fn render<T>(
                 ("stroke", "black")
             ));
 
-            let j = hbuild::single("path").with(attrs!(Line::new(it, ffmt)));
+            let j = hbuild::single("path").with(attrs!(
+                Line::new(it, ffmt).with_relative(canvas.simplify.is_some())
+            ));
             writer.put(g.append(j))?;
         }
         PlotType::Scatter => {
             let g = hbuild::elem("g").with(attrs!(
-                ("id", format_move!("poloto_plot{}", colori)),
+                ("id", format_move!("{}plot{}", canvas.id_prefix, colori)),
                 (
                     "class",
                     format_move!(
@@ -450,7 +726,7 @@ fn render<T>(
         }
         PlotType::Histo => {
             let g = hbuild::elem("g").with(attrs!(
-                ("id", format_move!("poloto_plot{}", colori)),
+                ("id", format_move!("{}plot{}", canvas.id_prefix, colori)),
                 (
                     "class",
                     format_move!(
@@ -479,7 +755,7 @@ fn render<T>(
         }
         PlotType::LineFill => {
             let g = hbuild::elem("g").with(attrs!(
-                ("id", format_move!("poloto_plot{}", colori)),
+                ("id", format_move!("{}plot{}", canvas.id_prefix, colori)),
                 (
                     "class",
                     format_move!(
@@ -494,12 +770,46 @@ fn render<T>(
                 ffmt,
                 height - paddingy,
                 true
-            )));
+            )
+            .with_relative(canvas.simplify.is_some())));
+            writer.put(g.append(j))?;
+        }
+        PlotType::LineSmooth => {
+            let g = hbuild::elem("g").with(attrs!(
+                ("id", format_move!("{}plot{}", canvas.id_prefix, colori)),
+                (
+                    "class",
+                    format_move!(
+                        "poloto_plot poloto_imgs poloto_line poloto_smooth poloto{} poloto_stroke",
+                        colori
+                    )
+                ),
+                ("fill", "none"),
+                ("stroke", "black")
+            ));
+
+            let j = hbuild::single("path").with(attrs!(Smooth::new(it, ffmt, None)));
+            writer.put(g.append(j))?;
+        }
+        PlotType::LineFillSmooth => {
+            let g = hbuild::elem("g").with(attrs!(
+                ("id", format_move!("{}plot{}", canvas.id_prefix, colori)),
+                (
+                    "class",
+                    format_move!(
+                        "poloto_plot poloto_imgs poloto_linefill poloto_smooth poloto{} poloto_fill",
+                        colori
+                    ),
+                )
+            ));
+
+            let j =
+                hbuild::single("path").with(attrs!(Smooth::new(it, ffmt, Some(height - paddingy))));
             writer.put(g.append(j))?;
         }
         PlotType::LineFillRaw => {
             let g = hbuild::elem("g").with(attrs!(
-                ("id", format_move!("poloto_plot{}", colori)),
+                ("id", format_move!("{}plot{}", canvas.id_prefix, colori)),
                 (
                     "class",
                     format_move!(
@@ -513,13 +823,14 @@ fn render<T>(
                 ffmt,
                 height - paddingy,
                 false
-            )));
+            )
+            .with_relative(canvas.simplify.is_some())));
 
             writer.put(g.append(j))?;
         }
         PlotType::Bars => {
             let g = hbuild::elem("g").with(attrs!(
-                ("id", format_move!("poloto_plot{}", colori)),
+                ("id", format_move!("{}plot{}", canvas.id_prefix, colori)),
                 (
                     "class",
                     format_move!(
@@ -542,15 +853,956 @@ fn render<T>(
             });
             writer.put(g.append(h))?;
         }
+        PlotType::Step(step) => {
+            let g = hbuild::elem("g").with(attrs!(
+                ("id", format_move!("{}plot{}", canvas.id_prefix, colori)),
+                (
+                    "class",
+                    format_move!(
+                        "poloto_plot poloto_imgs poloto_line poloto_step poloto{} poloto_stroke",
+                        colori
+                    )
+                ),
+                ("fill", "none"),
+                ("stroke", "black")
+            ));
+
+            let j = hbuild::single("path").with(attrs!(Line::new(it, ffmt).with_step(step)));
+            writer.put(g.append(j))?;
+        }
+        PlotType::Marker(marker) => {
+            let g = hbuild::elem("g").with(attrs!(
+                ("id", format_move!("{}plot{}", canvas.id_prefix, colori)),
+                (
+                    "class",
+                    format_move!(
+                        "poloto_plot poloto_imgs poloto_marker poloto{} poloto_fill poloto_stroke",
+                        colori
+                    ),
+                )
+            ));
+
+            let j =
+                hbuild::single("path").with(attrs!(marker_path(it, marker, padding / 30.0, ffmt)));
+            writer.put(g.append(j))?;
+        }
+        PlotType::LineMarker(marker) => {
+            let g = hbuild::elem("g").with(attrs!(
+                ("id", format_move!("{}plot{}", canvas.id_prefix, colori)),
+                (
+                    "class",
+                    format_move!("poloto_plot poloto_imgs poloto_line poloto{}", colori)
+                )
+            ));
+
+            let points: Vec<_> = it.collect();
+
+            let line = hbuild::single("path").with(attrs!(
+                ("class", format_move!("poloto{} poloto_stroke", colori)),
+                ("fill", "none"),
+                Line::new(points.clone().into_iter(), ffmt)
+            ));
+            let shapes = hbuild::single("path").with(attrs!(
+                (
+                    "class",
+                    format_move!("poloto_marker poloto{} poloto_fill poloto_stroke", colori)
+                ),
+                marker_path(points.into_iter(), marker, padding / 30.0, ffmt)
+            ));
+            writer.put(g.append(line.chain(shapes)))?;
+        }
+        PlotType::Heatmap(_) => unreachable!("heatmaps are rendered by render_heatmap"),
+        PlotType::Bubble(_) => unreachable!("bubbles are rendered by render_bubble"),
+        PlotType::Candle => unreachable!("candles are rendered by render_candles"),
+        PlotType::Quiver(_) => unreachable!("arrows are rendered by render_quiver"),
     };
     Ok(())
 }
 
+fn render_refline<T>(
+    writer: &mut ElemStack<T>,
+    canvas: &RenderFrame,
+    text: &str,
+    pos: &[[f64; 2]],
+    kind: build::refline::RefLineKind,
+) -> fmt::Result {
+    use build::refline::RefLineKind;
+
+    let ffmt = FloatFmt::new(canvas.precision);
+
+    //The plot area, so that the line goes all the way across no matter the data.
+    let left = canvas.xaspect_offset + canvas.padding;
+    let right = left + canvas.boundx.max;
+    let bottom = canvas.yaspect_offset + canvas.height - canvas.paddingy;
+    let top = bottom - canvas.boundy.max;
+
+    let vertical = matches!(kind, RefLineKind::VLine | RefLineKind::VSpan);
+    let k = if vertical { 0 } else { 1 };
+    let (a, b) = match pos {
+        [a, b] => (a[k].min(b[k]), a[k].max(b[k])),
+        _ => return Ok(()),
+    };
+    if !(a.is_finite() && b.is_finite()) {
+        return Ok(());
+    }
+
+    //Clip to the plot area in case the line didn't grow it.
+    let [lo, hi] = if vertical {
+        [left, right]
+    } else {
+        [top, bottom]
+    };
+    let eps = 1e-9;
+    if b < lo - eps || a > hi + eps {
+        return Ok(());
+    }
+    let (a, b) = (a.max(lo), b.min(hi));
+
+    let gap = canvas.padding / 20.0;
+    match kind {
+        RefLineKind::HLine | RefLineKind::VLine => {
+            let (class, [x1, y1, x2, y2]) = if vertical {
+                (
+                    "poloto_imgs poloto_refline poloto_vline",
+                    [a, top, a, bottom],
+                )
+            } else {
+                (
+                    "poloto_imgs poloto_refline poloto_hline",
+                    [left, a, right, a],
+                )
+            };
+            writer.put(hbuild::single("line").with(attrs!(
+                ("class", class),
+                ("x1", ffmt.disp(x1)),
+                ("y1", ffmt.disp(y1)),
+                ("x2", ffmt.disp(x2)),
+                ("y2", ffmt.disp(y2))
+            )))?;
+        }
+        RefLineKind::HSpan | RefLineKind::VSpan => {
+            let (class, [x, y, w, h]) = if vertical {
+                (
+                    "poloto_imgs poloto_refline poloto_vspan",
+                    [a, top, b - a, bottom - top],
+                )
+            } else {
+                (
+                    "poloto_imgs poloto_refline poloto_hspan",
+                    [left, a, right - left, b - a],
+                )
+            };
+            writer.put(hbuild::single("rect").with(attrs!(
+                ("class", class),
+                ("x", ffmt.disp(x)),
+                ("y", ffmt.disp(y)),
+                ("width", ffmt.disp(w)),
+                ("height", ffmt.disp(h))
+            )))?;
+        }
+    }
+
+    if text.is_empty() {
+        return Ok(());
+    }
+
+    //Vertical lines are labeled at the top, horizontal ones at the right.
+    let (x, y, anchor, baseline) = if vertical {
+        (a + gap, top + gap, "start", "hanging")
+    } else {
+        (right - gap, a - gap, "end", "auto")
+    };
+
+    writer.put(
+        hbuild::elem("text")
+            .with(attrs!(
+                ("class", "poloto_text poloto_refline"),
+                ("x", ffmt.disp(x)),
+                ("y", ffmt.disp(y)),
+                ("text-anchor", anchor),
+                ("dominant-baseline", baseline)
+            ))
+            .append(hbuild::raw(text.to_string()))
+            .inline(),
+    )
+}
+
+fn render_annotation<T>(
+    writer: &mut ElemStack<T>,
+    canvas: &RenderFrame,
+    text: &str,
+    [x, y]: [f64; 2],
+    style: build::annotate::AnnotationStyle,
+) -> fmt::Result {
+    if !(x.is_finite() && y.is_finite()) {
+        return Ok(());
+    }
+
+    let ffmt = FloatFmt::new(canvas.precision);
+    let [dx, dy] = style.offset;
+    let [tx, ty] = [x + dx, y - dy];
+
+    if style.arrow && (dx != 0.0 || dy != 0.0) {
+        //Two short strokes angled back from the tip.
+        let len = dx.hypot(dy);
+        let [bx, by] = [(tx - x) / len, (ty - y) / len];
+        let head = canvas.padding / 20.0;
+        let (sin, cos) = 0.45f64.sin_cos();
+        let h1 = [
+            x + head * (bx * cos - by * sin),
+            y + head * (bx * sin + by * cos),
+        ];
+        let h2 = [
+            x + head * (bx * cos + by * sin),
+            y + head * (by * cos - bx * sin),
+        ];
+
+        writer.put(hbuild::single("path").with(attrs!(
+            ("class", "poloto_imgs poloto_annotation"),
+            hbuild::path_from_closure(move |w| {
+                let mut w = w.start();
+                use tagu::attr::PathCommand::*;
+                w.put(M(ffmt.disp(tx), ffmt.disp(ty)))?;
+                w.put(L(ffmt.disp(x), ffmt.disp(y)))?;
+                w.put(M(ffmt.disp(h1[0]), ffmt.disp(h1[1])))?;
+                w.put(L(ffmt.disp(x), ffmt.disp(y)))?;
+                w.put(L(ffmt.disp(h2[0]), ffmt.disp(h2[1])))?;
+                Ok(())
+            })
+        )))?;
+    }
+
+    //Keep the text on the far side of its anchor from the point.
+    let baseline = if dy > 0.0 {
+        "auto"
+    } else if dy < 0.0 {
+        "hanging"
+    } else {
+        "middle"
+    };
+
+    writer.put(
+        hbuild::elem("text")
+            .with(attrs!(
+                ("class", "poloto_text poloto_annotation"),
+                ("x", ffmt.disp(tx)),
+                ("y", ffmt.disp(ty)),
+                ("dominant-baseline", baseline)
+            ))
+            .append(hbuild::raw(text.to_string()))
+            .inline(),
+    )
+}
+
+fn render_quiver<T>(
+    writer: &mut ElemStack<T>,
+    it: impl Iterator<Item = ([f64; 2], Option<[f64; 2]>)>,
+    style: build::quiver::QuiverStyle,
+    info: PlotRenderInfo,
+) -> fmt::Result {
+    let PlotRenderInfo {
+        canvas,
+        colori,
+        index,
+        precision,
+        ..
+    } = info;
+
+    let ffmt = FloatFmt::new(precision);
+
+    let g = hbuild::elem("g").with(attrs!(
+        ("id", format_move!("{}plot{}", canvas.id_prefix, colori)),
+        (
+            "class",
+            format_move!(
+                "poloto_plot poloto_imgs poloto_quiver poloto{} poloto_stroke",
+                colori
+            )
+        )
+    ));
+
+    let tails: Vec<_> = it
+        .filter_map(|(p, v)| Some((p, v?)))
+        .filter(|([x, y], _)| x.is_finite() && y.is_finite())
+        .collect();
+
+    //Arrows are sized on screen, with the x part relative to the width of the plot area
+    //and the y part relative to its height.
+    let [width, height] = [canvas.boundx.max, canvas.boundy.max];
+    let scale = style.scale.unwrap_or_else(|| {
+        if style.max <= 0.0 {
+            return 0.0;
+        }
+        let spacing = |k: usize, extent: f64| {
+            let mut vals: Vec<_> = tails.iter().map(|(p, _)| p[k]).collect();
+            vals.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            vals.windows(2)
+                .map(|w| w[1] - w[0])
+                .filter(|&d| d > 0.0)
+                .reduce(f64::min)
+                .map(|d| d / extent)
+        };
+        let fraction = match (spacing(0, width), spacing(1, height)) {
+            (Some(a), Some(b)) => a.min(b),
+            (a, b) => a.or(b).unwrap_or(0.1),
+        };
+        fraction / style.max
+    });
+
+    let arrows: Vec<_> = tails
+        .into_iter()
+        .map(|([x, y], [dx, dy])| {
+            let tip = [x + dx * scale * width, y - dy * scale * height];
+            ([x, y], tip, dx.hypot(dy))
+        })
+        .collect();
+
+    //Markers are painted explicitly instead of taking the color of their line, since
+    //not every renderer supports that. Each color gets its own marker.
+    let colors: Vec<_> = arrows
+        .iter()
+        .map(|&(_, _, magnitude)| style.colormap.map(|c| c.color_in(magnitude, style.range)))
+        .collect();
+
+    let mut palette: Vec<[u8; 3]> = vec![];
+    for &c in colors.iter().flatten() {
+        if !palette.contains(&c) {
+            palette.push(c);
+        }
+    }
+
+    let prefix = &canvas.id_prefix;
+    let mut markers = vec![];
+    if colors.iter().any(Option::is_none) || style.colormap.is_none() {
+        markers.push((
+            format!("{}arrow{}", prefix, index),
+            ("class", format!("poloto{} poloto_fill", colori)),
+        ));
+    }
+    if let Some(colormap) = style.colormap {
+        let [r, g, b] = colormap.color(0.5);
+        markers.push((
+            format!("{}arrow{}_legend", prefix, index),
+            ("fill", format!("rgb({},{},{})", r, g, b)),
+        ));
+    }
+    for (k, [r, g, b]) in palette.iter().enumerate() {
+        markers.push((
+            format!("{}arrow{}_{}", prefix, index, k),
+            ("fill", format!("rgb({},{},{})", r, g, b)),
+        ));
+    }
+
+    let defs =
+        hbuild::elem("defs").append(hbuild::from_iter(markers.into_iter().map(|(id, paint)| {
+            hbuild::elem("marker")
+                .with(attrs!(
+                    ("id", id),
+                    ("viewBox", "0 0 10 10"),
+                    ("refX", 9),
+                    ("refY", 5),
+                    ("markerWidth", 6),
+                    ("markerHeight", 6),
+                    ("orient", "auto-start-reverse")
+                ))
+                .append(hbuild::single("path").with(attrs!(
+                    ("d", "M 0 0 L 10 5 L 0 10 Z"),
+                    paint,
+                    ("stroke", "none")
+                )))
+        })));
+
+    let h = hbuild::from_stack(move |mut w| {
+        for (([x1, y1], [x2, y2], magnitude), color) in arrows.into_iter().zip(colors) {
+            let marker = match color.and_then(|c| palette.iter().position(|&p| p == c)) {
+                Some(k) => format!("url(#{}arrow{}_{})", prefix, index, k),
+                None => format!("url(#{}arrow{})", prefix, index),
+            };
+
+            let stroke = color.map(|[r, g, b]| ("stroke", format_move!("rgb({},{},{})", r, g, b)));
+
+            let title = hbuild::elem("title")
+                .append(hbuild::raw(format_move!("{}", magnitude)))
+                .inline();
+
+            w.put(
+                hbuild::elem("line")
+                    .with(attrs!(
+                        ("x1", ffmt.disp(x1)),
+                        ("y1", ffmt.disp(y1)),
+                        ("x2", ffmt.disp(x2)),
+                        ("y2", ffmt.disp(y2)),
+                        ("marker-end", marker),
+                        stroke
+                    ))
+                    .append(title),
+            )?;
+        }
+        Ok(w)
+    });
+
+    writer.put(g.append(defs).append(h))
+}
+
+fn render_candles<T>(
+    writer: &mut ElemStack<T>,
+    it: impl Iterator<Item = [f64; 2]>,
+    info: PlotRenderInfo,
+) -> fmt::Result {
+    let PlotRenderInfo {
+        canvas,
+        colori,
+        precision,
+        ..
+    } = info;
+
+    let ffmt = FloatFmt::new(precision);
+
+    let points: Vec<_> = it.collect();
+    let candles: Vec<_> = points
+        .chunks_exact(4)
+        .filter(|c| c.iter().all(|[x, y]| x.is_finite() && y.is_finite()))
+        .map(|c| [c[0][0], c[0][1], c[1][1], c[2][1], c[3][1]])
+        .collect();
+
+    //Make the bodies a bit narrower than the closest two candles.
+    let mut xs: Vec<_> = candles.iter().map(|c| c[0]).collect();
+    xs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let width = xs
+        .windows(2)
+        .map(|w| w[1] - w[0])
+        .filter(|&d| d > 0.0)
+        .reduce(f64::min)
+        .map(|d| d * 0.7)
+        .unwrap_or(canvas.padding / 10.0);
+
+    let g = hbuild::elem("g").with(attrs!(
+        ("id", format_move!("{}plot{}", canvas.id_prefix, colori)),
+        (
+            "class",
+            format_move!("poloto_plot poloto_imgs poloto_candle poloto{}", colori)
+        )
+    ));
+
+    let h = hbuild::from_iter(candles.into_iter().map(move |[x, open, high, low, close]| {
+        //The y axis is flipped, so a higher close has a smaller y.
+        let class = if close <= open {
+            "poloto_candle_up"
+        } else {
+            "poloto_candle_down"
+        };
+        let wick = hbuild::single("line").with(attrs!(
+            ("x1", ffmt.disp(x)),
+            ("x2", ffmt.disp(x)),
+            ("y1", ffmt.disp(high)),
+            ("y2", ffmt.disp(low))
+        ));
+        let body = hbuild::single("rect").with(attrs!(
+            ("x", ffmt.disp(x - width / 2.0)),
+            ("y", ffmt.disp(open.min(close))),
+            ("width", ffmt.disp(width)),
+            ("height", ffmt.disp((open - close).abs()))
+        ));
+        hbuild::elem("g")
+            .with(("class", class))
+            .append(wick.chain(body))
+    }));
+
+    writer.put(g.append(h))
+}
+
+fn render_heatmap<T>(
+    writer: &mut ElemStack<T>,
+    it: impl Iterator<Item = ([f64; 2], Option<f64>)>,
+    style: build::heatmap::HeatmapStyle,
+    info: PlotRenderInfo,
+) -> fmt::Result {
+    let PlotRenderInfo {
+        canvas,
+        colori,
+        precision,
+        ..
+    } = info;
+
+    let ffmt = FloatFmt::new(precision);
+
+    let g = hbuild::elem("g").with(attrs!(
+        ("id", format_move!("{}plot{}", canvas.id_prefix, colori)),
+        (
+            "class",
+            format_move!("poloto_plot poloto_imgs poloto_heatmap poloto{}", colori)
+        )
+    ));
+
+    let h = hbuild::from_stack(move |mut w| {
+        let mut it = it;
+        while let (Some(([x1, y1], _)), Some(([x2, y2], value))) = (it.next(), it.next()) {
+            let Some(value) = value.filter(|v| v.is_finite()) else {
+                continue;
+            };
+            if ![x1, y1, x2, y2].iter().all(|a| a.is_finite()) {
+                continue;
+            }
+
+            let [r, g, b] = style.colormap.color_in(value, style.range);
+
+            w.put(
+                hbuild::elem("rect")
+                    .with(attrs!(
+                        ("class", "poloto_cell"),
+                        ("x", ffmt.disp(x1.min(x2))),
+                        ("y", ffmt.disp(y1.min(y2))),
+                        ("width", ffmt.disp((x2 - x1).abs())),
+                        ("height", ffmt.disp((y2 - y1).abs())),
+                        ("fill", format_move!("rgb({},{},{})", r, g, b))
+                    ))
+                    .append(
+                        hbuild::elem("title")
+                            .append(hbuild::raw(format_move!("{}", value)))
+                            .inline(),
+                    ),
+            )?;
+        }
+        Ok(w)
+    });
+    writer.put(g.append(h))
+}
+
+fn render_bubble<T>(
+    writer: &mut ElemStack<T>,
+    it: impl Iterator<Item = ([f64; 2], Option<f64>, Option<f64>)>,
+    style: build::bubble::BubbleStyle,
+    info: PlotRenderInfo,
+) -> fmt::Result {
+    let PlotRenderInfo {
+        canvas,
+        colori,
+        precision,
+        ..
+    } = info;
+
+    let ffmt = FloatFmt::new(precision);
+
+    //Circles are colored individually if there is a colormap.
+    let class = if style.colormap.is_some() {
+        ""
+    } else {
+        "poloto_fill"
+    };
+
+    let g = hbuild::elem("g").with(attrs!(
+        ("id", format_move!("{}plot{}", canvas.id_prefix, colori)),
+        (
+            "class",
+            format_move!(
+                "poloto_plot poloto_imgs poloto_bubble poloto{} {}",
+                colori,
+                class
+            )
+        )
+    ));
+
+    let h = hbuild::from_stack(move |mut w| {
+        for ([x, y], size, color) in it {
+            let Some(size) = size.filter(|s| s.is_finite()) else {
+                continue;
+            };
+            if !(x.is_finite() && y.is_finite()) {
+                continue;
+            }
+
+            let fill = style
+                .colormap
+                .zip(color.filter(|c| c.is_finite()))
+                .map(|(c, v)| c.color_in(v, style.color_range))
+                .map(|[r, g, b]| ("fill", format_move!("rgb({},{},{})", r, g, b)));
+
+            w.put(
+                hbuild::elem("circle")
+                    .with(attrs!(
+                        ("cx", ffmt.disp(x)),
+                        ("cy", ffmt.disp(y)),
+                        ("r", ffmt.disp(style.radius(size))),
+                        fill
+                    ))
+                    .append(
+                        hbuild::elem("title")
+                            .append(hbuild::raw(format_move!("{}", size)))
+                            .inline(),
+                    ),
+            )?;
+        }
+        Ok(w)
+    });
+    writer.put(g.append(h))
+}
+
+///
+/// Draw nested circles of a few reference sizes in the legend column starting at `top`.
+/// Returns where the next item in the legend column can start.
+///
+fn render_size_legend<T>(
+    writer: &mut ElemStack<T>,
+    canvas: &RenderFrame,
+    style: build::bubble::BubbleStyle,
+    top: f64,
+) -> Result<f64, fmt::Error> {
+    let RenderFrame {
+        legendx1,
+        spacing,
+        precision,
+        ..
+    } = *canvas;
+
+    if style.max_size <= 0.0 {
+        return Ok(top);
+    }
+
+    let ffmt = FloatFmt::new(precision);
+
+    //Largest number of the form 1, 2 or 5 times a power of ten that fits.
+    let mag = 10f64.powf(style.max_size.log10().floor());
+    let nice = [5.0, 2.0, 1.0]
+        .iter()
+        .map(|m| m * mag)
+        .find(|&s| s <= style.max_size)
+        .unwrap_or(mag);
+
+    let cx = legendx1 + style.max_radius;
+    let bottom = top + spacing / 2.0 + 2.0 * style.max_radius;
+
+    let sizes = [nice, nice / 2.0, nice / 5.0];
+
+    let circles = hbuild::from_iter(sizes.into_iter().map(move |s| {
+        let r = style.radius(s);
+        hbuild::single("circle").with(attrs!(
+            ("class", "poloto_imgs poloto_ticks poloto_size_legend"),
+            ("cx", ffmt.disp(cx)),
+            ("cy", ffmt.disp(bottom - r)),
+            ("r", ffmt.disp(r))
+        ))
+    }));
+
+    let texts = hbuild::from_iter(sizes.into_iter().map(move |s| {
+        hbuild::elem("text")
+            .with(attrs!(
+                ("class", "poloto_text poloto_size_legend"),
+                ("x", ffmt.disp(cx + style.max_radius + spacing / 4.0)),
+                ("y", ffmt.disp(bottom - 2.0 * style.radius(s)))
+            ))
+            .append(hbuild::raw(format_move!("{}", s)))
+            .inline()
+    }));
+
+    writer.put(
+        hbuild::elem("g")
+            .with(("class", "poloto_size_legend"))
+            .append(circles.chain(texts)),
+    )?;
+
+    Ok(bottom + spacing / 2.0)
+}
+
+///
+/// Draw a vertical colorbar in the legend column between `top` and `bottom`,
+/// with ticks generated by the default float tick generator.
+///
+fn render_colorbar<T>(
+    writer: &mut ElemStack<T>,
+    canvas: &RenderFrame,
+    colormap: crate::colormap::Colormap,
+    [min, max]: [f64; 2],
+    [top, bottom]: [f64; 2],
+) -> fmt::Result {
+    let RenderFrame {
+        padding,
+        legendx1,
+        precision,
+        ..
+    } = *canvas;
+
+    let length = bottom - top;
+    if length < padding / 3.0 {
+        return Ok(());
+    }
+
+    let ffmt = FloatFmt::new(precision);
+    let bar_width = padding / 10.0;
+    let num_segments = 32;
+    let seg = length / num_segments as f64;
+
+    let bar = hbuild::from_iter((0..num_segments).map(move |i| {
+        let [r, g, b] = colormap.color(1.0 - (i as f64 + 0.5) / num_segments as f64);
+        hbuild::single("rect").with(attrs!(
+            ("x", ffmt.disp(legendx1)),
+            ("y", ffmt.disp(top + i as f64 * seg)),
+            ("width", ffmt.disp(bar_width)),
+            //Overlap slightly to avoid seams between segments.
+            ("height", ffmt.disp(seg + 0.5)),
+            ("fill", format_move!("rgb({},{},{})", r, g, b))
+        ))
+    }));
+
+    let mut counter = 0;
+    let ticks = f64::default_ticks()
+        .generate(
+            &DataBound { min, max },
+            &RenderFrameBound {
+                ideal_num_steps: ((length / 60.0).floor() as u32).max(2),
+                ideal_dash_size: 20.0,
+                max: length,
+                axis: Axis::Y,
+            },
+            IndexRequester::new(&mut counter),
+        )
+        .unwrap();
+
+    use crate::ticks::tick_fmt::TickFmt;
+    let mut labels = vec![];
+    for val in ticks.iter.into_iter().filter(|v| *v >= min && *v <= max) {
+        let mut s = String::new();
+        ticks.fmt.write_tick(&mut s, &val)?;
+        let offset = val.scale(&[min, max], length) - min.scale(&[min, max], length);
+        labels.push((bottom - offset, s));
+    }
+    let mut where_fmt = String::new();
+    ticks.fmt.write_where(&mut where_fmt)?;
+
+    let tick_ys: Vec<f64> = labels.iter().map(|(y, _)| *y).collect();
+    let tick_lines = hbuild::single("path").with(attrs!(
+        ("class", "poloto_imgs poloto_ticks poloto_colorbar"),
+        hbuild::path_from_closure(move |w| {
+            let mut w = w.start();
+            use tagu::attr::PathCommand::*;
+            for y in tick_ys {
+                w.put(M(ffmt.disp(legendx1 + bar_width), ffmt.disp(y)))?;
+                w.put(H_(ffmt.disp(bar_width / 2.0)))?;
+            }
+            Ok(())
+        })
+    ));
+
+    let texts = hbuild::from_iter(labels.into_iter().map(move |(y, s)| {
+        hbuild::elem("text")
+            .with(attrs!(
+                ("class", "poloto_text poloto_colorbar"),
+                ("x", ffmt.disp(legendx1 + bar_width * 2.0)),
+                ("y", ffmt.disp(y))
+            ))
+            .append(hbuild::raw(s))
+            .inline()
+    }));
+
+    let where_text = (!where_fmt.is_empty()).then(|| {
+        hbuild::elem("text")
+            .with(attrs!(
+                ("class", "poloto_text poloto_colorbar"),
+                ("x", ffmt.disp(legendx1)),
+                ("y", ffmt.disp(bottom + padding / 8.0))
+            ))
+            .append(hbuild::raw(where_fmt))
+            .inline()
+    });
+
+    writer.put(
+        hbuild::elem("g")
+            .with(("class", "poloto_colorbar"))
+            .append(bar.chain(tick_lines).chain(texts).chain(where_text)),
+    )
+}
+
+///
+/// Draw a marker shape of half width `size` centered at each finite point, all in one path.
+///
+fn marker_path(
+    it: impl Iterator<Item = [f64; 2]>,
+    marker: Marker,
+    size: f64,
+    ffmt: FloatFmt,
+) -> impl attr::Attr {
+    hbuild::path_from_closure(move |w| {
+        let mut w = w.start();
+        use tagu::attr::PathCommand::*;
+        let s = size;
+        let d = |a: f64| ffmt.disp(a);
+        for [x, y] in it.filter(|&[x, y]| x.is_finite() && y.is_finite()) {
+            match marker {
+                Marker::Circle => {
+                    //Four cubic bezier quarters.
+                    let k = 0.5523 * s;
+                    w.put(M(d(x + s), d(y)))?;
+                    w.put(C_(d(0.0), d(k), d(k - s), d(s), d(-s), d(s)))?;
+                    w.put(C_(d(-k), d(0.0), d(-s), d(k - s), d(-s), d(-s)))?;
+                    w.put(C_(d(0.0), d(-k), d(s - k), d(-s), d(s), d(-s)))?;
+                    w.put(C_(d(k), d(0.0), d(s), d(s - k), d(s), d(s)))?;
+                    w.put(Z())?;
+                }
+                Marker::Square => {
+                    w.put(M(d(x - s), d(y - s)))?;
+                    w.put(H_(d(2.0 * s)))?;
+                    w.put(V_(d(2.0 * s)))?;
+                    w.put(H_(d(-2.0 * s)))?;
+                    w.put(Z())?;
+                }
+                Marker::Triangle => {
+                    w.put(M(d(x), d(y - s)))?;
+                    w.put(L_(d(s), d(2.0 * s)))?;
+                    w.put(H_(d(-2.0 * s)))?;
+                    w.put(Z())?;
+                }
+                Marker::Diamond => {
+                    w.put(M(d(x), d(y - s)))?;
+                    w.put(L_(d(s), d(s)))?;
+                    w.put(L_(d(-s), d(s)))?;
+                    w.put(L_(d(-s), d(-s)))?;
+                    w.put(Z())?;
+                }
+                Marker::Cross => {
+                    w.put(M(d(x - s), d(y - s)))?;
+                    w.put(L_(d(2.0 * s), d(2.0 * s)))?;
+                    w.put(M_(d(0.0), d(-2.0 * s)))?;
+                    w.put(L_(d(-2.0 * s), d(2.0 * s)))?;
+                }
+                Marker::Plus => {
+                    w.put(M(d(x - s), d(y)))?;
+                    w.put(H_(d(2.0 * s)))?;
+                    w.put(M_(d(-s), d(-s)))?;
+                    w.put(V_(d(2.0 * s)))?;
+                }
+            }
+        }
+        Ok(())
+    })
+}
+
+///
+/// A curve through the points made of cubic beziers, using monotone cubic interpolation
+/// so that it never overshoots the data. Holes split the curve like with [`Line`].
+/// If there is a base line, each part is closed against it so that it can be filled.
+///
+struct Smooth<I> {
+    it: I,
+    fmt: FloatFmt,
+    base_line: Option<f64>,
+}
+impl<I: Iterator<Item = [f64; 2]>> Smooth<I> {
+    pub fn new(it: I, fmt: FloatFmt, base_line: Option<f64>) -> Self {
+        Smooth { it, fmt, base_line }
+    }
+}
+impl<I: Iterator<Item = [f64; 2]>> attr::Attr for Smooth<I> {
+    fn render(self, w: &mut attr::AttrWrite) -> fmt::Result {
+        let Smooth { it, fmt, base_line } = self;
+
+        w.render(tagu::build::path_from_closure(|w| {
+            let mut w = w.start();
+            use tagu::attr::PathCommand::*;
+
+            let mut run = vec![];
+            let mut it = it.fuse();
+            loop {
+                let next = it.next();
+                if let Some(p) = next.filter(|[x, y]| x.is_finite() && y.is_finite()) {
+                    run.push(p);
+                    continue;
+                }
+
+                if let (Some(&[x0, y0]), Some(&[xn, _])) = (run.first(), run.last()) {
+                    if let Some(base) = base_line {
+                        w.put(M(fmt.disp(x0), fmt.disp(base)))?;
+                        w.put(L(fmt.disp(x0), fmt.disp(y0)))?;
+                    } else {
+                        w.put(M(fmt.disp(x0), fmt.disp(y0)))?;
+                    }
+
+                    let tangents = monotone_tangents(&run);
+                    for (k, pair) in run.windows(2).enumerate() {
+                        let ([x0, y0], [x1, y1]) = (pair[0], pair[1]);
+                        let h = (x1 - x0) / 3.0;
+                        w.put(C(
+                            fmt.disp(x0 + h),
+                            fmt.disp(y0 + tangents[k] * h),
+                            fmt.disp(x1 - h),
+                            fmt.disp(y1 - tangents[k + 1] * h),
+                            fmt.disp(x1),
+                            fmt.disp(y1),
+                        ))?;
+                    }
+
+                    if let Some(base) = base_line {
+                        w.put(L(fmt.disp(xn), fmt.disp(base)))?;
+                        w.put(Z())?;
+                    }
+                }
+                run.clear();
+
+                if next.is_none() {
+                    break;
+                }
+            }
+            Ok(())
+        }))
+    }
+}
+
+///
+/// Compute the slope at each point such that the cubic hermite spline through
+/// the points is monotone between them (Steffen's method).
+///
+pub(super) fn monotone_tangents(points: &[[f64; 2]]) -> Vec<f64> {
+    let n = points.len();
+    let secants: Vec<f64> = points
+        .windows(2)
+        .map(|p| {
+            let h = p[1][0] - p[0][0];
+            if h == 0.0 {
+                0.0
+            } else {
+                (p[1][1] - p[0][1]) / h
+            }
+        })
+        .collect();
+
+    (0..n)
+        .map(|k| {
+            if k == 0 {
+                secants.first().copied().unwrap_or(0.0)
+            } else if k == n - 1 {
+                secants[k - 1]
+            } else {
+                let (s0, s1) = (secants[k - 1], secants[k]);
+                if s0 * s1 <= 0.0 {
+                    return 0.0;
+                }
+                let h0 = points[k][0] - points[k - 1][0];
+                let h1 = points[k + 1][0] - points[k][0];
+                let p = (s0 * h1 + s1 * h0) / (h0 + h1);
+                s0.signum() * s0.abs().min(s1.abs()).min(0.5 * p.abs()) * 2.0
+            }
+        })
+        .collect()
+}
+
+///
+/// Write a line to the point, relative to the current point if the pen says so.
+///
+macro_rules! pen_line {
+    ($w:expr, $pen:expr, $fmt:expr, $x:expr, $y:expr) => {{
+        use tagu::attr::PathCommand::*;
+        match $pen.line_to($x, $y) {
+            Some(([a, b], true)) => $w.put(L_($fmt.disp(a), $fmt.disp(b))),
+            Some(([a, b], false)) => $w.put(L($fmt.disp(a), $fmt.disp(b))),
+            None => Ok(()),
+        }
+    }};
+}
+
 struct LineFill<I> {
     it: I,
     fmt: FloatFmt,
     base_line: f64,
     add_start_end_base: bool,
+    relative: bool,
 }
 impl<I: Iterator<Item = [f64; 2]>> LineFill<I> {
     pub fn new(it: I, fmt: FloatFmt, base_line: f64, add_start_end_base: bool) -> Self {
@@ -559,8 +1811,13 @@ impl<I: Iterator<Item = [f64; 2]>> LineFill<I> {
             fmt,
             base_line,
             add_start_end_base,
+            relative: false,
         }
     }
+    pub fn with_relative(mut self, relative: bool) -> Self {
+        self.relative = relative;
+        self
+    }
 }
 impl<I: Iterator<Item = [f64; 2]>> attr::Attr for LineFill<I> {
     fn render(self, w: &mut attr::AttrWrite) -> fmt::Result {
@@ -569,6 +1826,7 @@ impl<I: Iterator<Item = [f64; 2]>> attr::Attr for LineFill<I> {
             fmt,
             base_line,
             add_start_end_base,
+            relative,
         } = self;
 
         w.render(tagu::build::path_from_closure(|w| {
@@ -577,6 +1835,7 @@ impl<I: Iterator<Item = [f64; 2]>> attr::Attr for LineFill<I> {
             if let Some([startx, starty]) = it.next() {
                 use tagu::attr::PathCommand::*;
 
+                let mut pen = simplify::Pen::new(fmt.precision, relative);
                 let mut last = [startx, starty];
                 let mut last_finite = None;
                 let mut first = true;
@@ -589,20 +1848,23 @@ impl<I: Iterator<Item = [f64; 2]>> attr::Attr for LineFill<I> {
                             if first {
                                 if add_start_end_base {
                                     w.put(M(fmt.disp(last[0]), fmt.disp(base_line)))?;
-                                    w.put(L(fmt.disp(last[0]), fmt.disp(last[1])))?;
+                                    pen.move_to(last[0], base_line);
+                                    pen_line!(w, pen, fmt, last[0], last[1])?;
                                 } else {
                                     w.put(M(fmt.disp(last[0]), fmt.disp(last[1])))?;
+                                    pen.move_to(last[0], last[1]);
                                 }
                                 first = false;
                             }
                             last_finite = Some([newx, newy]);
-                            w.put(L(fmt.disp(newx), fmt.disp(newy)))?;
+                            pen_line!(w, pen, fmt, newx, newy)?;
                         }
                         (true, false) => {
                             w.put(M(fmt.disp(newx), fmt.disp(newy)))?;
+                            pen.move_to(newx, newy);
                         }
                         (false, true) => {
-                            w.put(L(fmt.disp(last[0]), fmt.disp(base_line)))?;
+                            pen_line!(w, pen, fmt, last[0], base_line)?;
                         }
                         _ => {}
                     };
@@ -610,7 +1872,7 @@ impl<I: Iterator<Item = [f64; 2]>> attr::Attr for LineFill<I> {
                 }
                 if let Some([x, _]) = last_finite {
                     if add_start_end_base {
-                        w.put(L(fmt.disp(x), fmt.disp(base_line)))?;
+                        pen_line!(w, pen, fmt, x, base_line)?;
                     }
                     w.put(Z())?;
                 }
@@ -624,15 +1886,35 @@ impl<I: Iterator<Item = [f64; 2]>> attr::Attr for LineFill<I> {
 struct Line<I> {
     it: I,
     fmt: FloatFmt,
+    step: Option<Step>,
+    relative: bool,
 }
 impl<I: Iterator<Item = [f64; 2]>> Line<I> {
     pub fn new(it: I, fmt: FloatFmt) -> Self {
-        Line { it, fmt }
+        Line {
+            it,
+            fmt,
+            step: None,
+            relative: false,
+        }
+    }
+    pub fn with_step(mut self, step: Step) -> Self {
+        self.step = Some(step);
+        self
+    }
+    pub fn with_relative(mut self, relative: bool) -> Self {
+        self.relative = relative;
+        self
     }
 }
 impl<I: Iterator<Item = [f64; 2]>> attr::Attr for Line<I> {
     fn render(self, w: &mut attr::AttrWrite) -> fmt::Result {
-        let Line { mut it, fmt } = self;
+        let Line {
+            mut it,
+            fmt,
+            step,
+            relative,
+        } = self;
 
         w.render(tagu::build::path_from_closure(|w| {
             let mut w = w.start();
@@ -640,6 +1922,7 @@ impl<I: Iterator<Item = [f64; 2]>> attr::Attr for Line<I> {
             if let Some([startx, starty]) = it.next() {
                 use tagu::attr::PathCommand::*;
 
+                let mut pen = simplify::Pen::new(fmt.precision, relative && step.is_none());
                 let mut last = [startx, starty];
                 let mut first = true;
                 for [newx, newy] in it {
@@ -650,12 +1933,29 @@ impl<I: Iterator<Item = [f64; 2]>> attr::Attr for Line<I> {
                         (true, true) => {
                             if first {
                                 w.put(M(fmt.disp(last[0]), fmt.disp(last[1])))?;
+                                pen.move_to(last[0], last[1]);
                                 first = false;
                             }
-                            w.put(L(fmt.disp(newx), fmt.disp(newy)))?;
+                            match step {
+                                None => pen_line!(w, pen, fmt, newx, newy)?,
+                                Some(Step::Pre) => {
+                                    w.put(V(fmt.disp(newy)))?;
+                                    w.put(H(fmt.disp(newx)))?;
+                                }
+                                Some(Step::Mid) => {
+                                    w.put(H(fmt.disp((last[0] + newx) / 2.0)))?;
+                                    w.put(V(fmt.disp(newy)))?;
+                                    w.put(H(fmt.disp(newx)))?;
+                                }
+                                Some(Step::Post) => {
+                                    w.put(H(fmt.disp(newx)))?;
+                                    w.put(V(fmt.disp(newy)))?;
+                                }
+                            }
                         }
                         (true, false) => {
                             w.put(M(fmt.disp(newx), fmt.disp(newy)))?;
+                            pen.move_to(newx, newy);
                         }
                         _ => {}
                     };
@@ -0,0 +1,136 @@
+//!
+//! Shrink the paths of line plots without visibly changing them.
+//!
+//! Consecutive points that are written out the same at the configured precision are dropped,
+//! points within a pixel tolerance of a straight line are removed with Ramer-Douglas-Peucker,
+//! and each line segment is written with relative coordinates when that is shorter.
+//!
+use crate::build::PlotType;
+
+fn round(precision: usize, v: f64) -> f64 {
+    format!("{:.*}", precision, v).parse().unwrap()
+}
+
+///
+/// Simplify the points if asked to and the plot is a line or a fill.
+///
+pub(super) fn apply<I: Iterator<Item = [f64; 2]>>(
+    tolerance: Option<f64>,
+    precision: usize,
+    p_type: PlotType,
+    it: I,
+) -> super::downsample::Points<I> {
+    use super::downsample::Points;
+
+    let tolerance = match (tolerance, p_type) {
+        (Some(t), PlotType::Line | PlotType::LineFill | PlotType::LineFillRaw) => t,
+        _ => return Points::Raw(it),
+    };
+
+    let mut out = vec![];
+    let mut segment: Vec<[f64; 2]> = vec![];
+    for p in it {
+        if !(p[0].is_finite() && p[1].is_finite()) {
+            rdp(&segment, tolerance, &mut out);
+            segment.clear();
+            out.push(p);
+            continue;
+        }
+
+        let r = p.map(|v| round(precision, v));
+        if segment.last().map(|l| l.map(|v| round(precision, v))) != Some(r) {
+            segment.push(p);
+        }
+    }
+    rdp(&segment, tolerance, &mut out);
+
+    Points::Reduced(out.into_iter())
+}
+
+fn rdp(points: &[[f64; 2]], tolerance: f64, out: &mut Vec<[f64; 2]>) {
+    if points.len() < 3 || tolerance <= 0.0 {
+        out.extend_from_slice(points);
+        return;
+    }
+
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[points.len() - 1] = true;
+
+    let mut stack = vec![(0, points.len() - 1)];
+    while let Some((a, b)) = stack.pop() {
+        let [ax, ay] = points[a];
+        let [bx, by] = points[b];
+        let len = (bx - ax).hypot(by - ay);
+
+        let dist = |[x, y]: [f64; 2]| {
+            if len == 0.0 {
+                (x - ax).hypot(y - ay)
+            } else {
+                ((bx - ax) * (ay - y) - (ax - x) * (by - ay)).abs() / len
+            }
+        };
+
+        let far =
+            (a + 1..b).max_by(|&i, &j| dist(points[i]).partial_cmp(&dist(points[j])).unwrap());
+        if let Some(far) = far {
+            if dist(points[far]) > tolerance {
+                keep[far] = true;
+                stack.push((a, far));
+                stack.push((far, b));
+            }
+        }
+    }
+
+    out.extend(points.iter().zip(keep).filter(|(_, k)| *k).map(|(p, _)| *p));
+}
+
+///
+/// Keeps track of the current point of a path so that line segments can be written relative to it.
+///
+pub(super) struct Pen {
+    precision: usize,
+    relative: bool,
+    cur: [f64; 2],
+}
+
+impl Pen {
+    pub fn new(precision: usize, relative: bool) -> Self {
+        Pen {
+            precision,
+            relative,
+            cur: [0.0; 2],
+        }
+    }
+
+    pub fn move_to(&mut self, x: f64, y: f64) {
+        self.cur = [x, y].map(|v| round(self.precision, v));
+    }
+
+    ///
+    /// The coordinates to write for a line to the point, and whether they are relative.
+    /// Lines that would not go anywhere are skipped.
+    ///
+    pub fn line_to(&mut self, x: f64, y: f64) -> Option<([f64; 2], bool)> {
+        if !self.relative {
+            return Some(([x, y], false));
+        }
+
+        //Work off of what was written so rounding errors don't add up.
+        let p = [x, y].map(|v| round(self.precision, v));
+        if p == self.cur {
+            return None;
+        }
+        let d = [p[0] - self.cur[0], p[1] - self.cur[1]];
+        self.cur = p;
+
+        let width = |[a, b]: [f64; 2]| {
+            format!("{:.*}", self.precision, a).len() + format!("{:.*}", self.precision, b).len()
+        };
+        Some(if width(d) < width(p) {
+            (d, true)
+        } else {
+            (p, false)
+        })
+    }
+}
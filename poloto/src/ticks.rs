@@ -53,6 +53,7 @@ impl<'a> IndexRequester<'a> {
 use tick_fmt::*;
 pub mod tick_fmt {
     use super::*;
+    #[derive(Copy, Clone)]
     pub struct DefaultTickFmt;
 
     impl<N: Display> TickFmt<N> for DefaultTickFmt {
@@ -74,6 +75,7 @@ pub mod tick_fmt {
         }
     }
 
+    #[derive(Copy, Clone)]
     pub struct WithWhereFmt<D, F> {
         ticks: D,
         func: F,
@@ -92,6 +94,7 @@ pub mod tick_fmt {
         }
     }
 
+    #[derive(Copy, Clone)]
     pub struct WithTickFmt<D, F> {
         ticks: D,
         func: F,
@@ -109,6 +112,7 @@ pub mod tick_fmt {
         }
     }
 
+    #[derive(Copy, Clone)]
     pub struct WithData<K, E> {
         ticks: K,
         pub data: E,
@@ -212,6 +216,7 @@ where
     }
 }
 
+#[derive(Copy, Clone, Debug)]
 pub struct TickRes {
     pub dash_size: Option<f64>,
 }
@@ -254,6 +259,7 @@ impl<I: IntoIterator, F: TickFmt<I::Item>> TickDist for TickDistribution<I, F> {
     }
 }
 
+#[derive(Clone)]
 pub struct TickDistribution<I, F> {
     pub iter: I,
     pub fmt: F,
@@ -0,0 +1,35 @@
+use super::*;
+use poloto::build::plot;
+
+#[test]
+fn annotate() {
+    use poloto::build::annotate;
+
+    let render = |grow: bool| {
+        let mut s = String::new();
+        poloto::frame_build()
+            .data(plots!(
+                plot("a").line([(0.0, 0.0), (2.0, 2.0)]),
+                annotate("peak", 1.0, 1.0)
+                    .with_offset([0.0, 20.0])
+                    .with_arrow(),
+                annotate("far", 10.0, 10.0).grow_area(grow)
+            ))
+            .build_and_label(("title", "x", "y"))
+            .headless()
+            .render_fmt_write(&mut s)
+            .unwrap();
+        s
+    };
+
+    let s = render(false);
+    assert!(s.contains(r#"<text class="poloto_text poloto_annotation" x="400.00" y="230.00" dominant-baseline="auto">peak</text>"#));
+    assert!(s.contains(
+        r#"<path class="poloto_imgs poloto_annotation" d=" M 400.00 230.00 L 400.00 250.00 "#
+    ));
+    //Annotations don't get a legend entry.
+    assert!(!s.contains("poloto_legend poloto_text poloto1"));
+    //The far away annotation is outside of the plot since it didn't grow the area.
+    assert!(s.contains(r#"x="2650.00""#));
+    assert!(!render(true).contains(r#"x="2650.00""#));
+}
@@ -0,0 +1,27 @@
+use poloto::build::plot;
+
+#[test]
+fn bubble() {
+    use poloto::build::bubble::{Bubble, Bubbles};
+
+    let bubbles = Bubbles::new([
+        Bubble::new(0.0, 0.0, 100.0).with_color(1.0),
+        Bubble::new(1.0, 1.0, 25.0).with_color(2.0),
+    ])
+    .with_max_radius(10.0)
+    .with_colormap(poloto::colormap::Colormap::grayscale());
+
+    let mut s = String::new();
+    poloto::frame_build()
+        .data(plot("b").bubble(bubbles))
+        .build_and_label(("title", "x", "y"))
+        .headless()
+        .render_fmt_write(&mut s)
+        .unwrap();
+
+    //Area is proportional to size.
+    assert!(s.contains("r=\"10.00\" fill=\"rgb(0,0,0)\">\n\t\t<title>100</title>"));
+    assert!(s.contains("r=\"5.00\" fill=\"rgb(255,255,255)\">\n\t\t<title>25</title>"));
+    assert!(s.contains(r#"class="poloto_size_legend""#));
+    assert!(s.contains(r#"class="poloto_colorbar""#));
+}
@@ -0,0 +1,26 @@
+use poloto::build::plot;
+
+#[test]
+fn candlestick() {
+    use poloto::build::candle::Candle;
+
+    let candles = [
+        Candle::new(0.0, 1.0, 4.0, 0.0, 3.0),
+        Candle::new(1.0, 3.0, 3.0, 1.0, 2.0),
+        Candle::new(3.0, 2.0, 2.0, 2.0, f64::NAN),
+    ];
+
+    let mut s = String::new();
+    poloto::frame_build()
+        .data(plot("a").candlestick(candles))
+        .build_and_label(("title", "x", "y"))
+        .headless()
+        .render_fmt_write(&mut s)
+        .unwrap();
+
+    assert_eq!(s.matches(r#"<g class="poloto_candle_up">"#).count(), 1);
+    assert_eq!(s.matches(r#"<g class="poloto_candle_down">"#).count(), 1);
+    //The bodies are a bit narrower than the spacing between the two candles.
+    assert!(s.contains(r#"<line x1="150.00" x2="150.00" y1="100.00" y2="400.00"/>"#));
+    assert!(s.contains(r#"<rect x="91.67" y="175.00" width="116.67" height="150.00"/>"#));
+}
@@ -0,0 +1,31 @@
+use super::*;
+use poloto::build::plot;
+
+#[test]
+fn contour() {
+    use poloto::build::contour::Contour;
+
+    let n = 21;
+    let coord = |k: usize| -1.0 + 2.0 * k as f64 / (n - 1) as f64;
+    let rows = (0..n).map(|j| (0..n).map(move |i| coord(i).powi(2) + coord(j).powi(2)));
+    let contour = Contour::new([-1.0, 1.0], [-1.0, 1.0], rows);
+
+    let circle = contour.lines(0.5);
+    assert!(circle
+        .iter()
+        .all(|(x, y)| (x * x + y * y - 0.5).abs() < 0.02));
+    let (first, last) = (circle[0], circle[circle.len() - 1]);
+    assert_eq!(first, last);
+
+    assert_eq!(contour.clone().with_num_levels(3).levels(), [0.5, 1.0, 1.5]);
+
+    let overlay = plot("center").scatter([[0.0, 0.0]]);
+    let data = poloto::frame_build()
+        .data(plots!(contour.with_levels([0.5, 1.0]).build(), overlay))
+        .build_and_label(("title", "x", "y"));
+
+    let mut s = String::new();
+    data.headless().render_fmt_write(&mut s).unwrap();
+    assert!(s.contains(">0.5<"));
+    assert!(s.contains(">center<"));
+}
@@ -0,0 +1,61 @@
+use super::*;
+use poloto::build::plot;
+
+#[test]
+fn distribution() {
+    use poloto::build::distribution::{Ecdf, Kde};
+
+    let samples = [3.0, 1.0, 2.0, 2.0, f64::NAN];
+
+    let e = Ecdf::new(samples);
+    assert_eq!(e.eval(0.0), 0.0);
+    assert_eq!(e.eval(2.0), 0.75);
+    assert_eq!(
+        e.points(),
+        [
+            (1.0, 0.0),
+            (1.0, 0.25),
+            (2.0, 0.25),
+            (2.0, 0.75),
+            (3.0, 0.75),
+            (3.0, 1.0)
+        ]
+    );
+
+    //One sample at zero with a bandwidth of one is the standard normal distribution.
+    let k = Kde::new([0.0]).with_bandwidth(1.0);
+    assert!((k.eval(0.0) - 0.398_942_280).abs() < 1e-9);
+
+    let k = Kde::new(samples).with_num_points(1000);
+    let points = k.points();
+    assert_eq!(points.len(), 1000);
+    let step = points[1].0 - points[0].0;
+    let area: f64 = points.iter().map(|(_, y)| y * step).sum();
+    assert!((area - 1.0).abs() < 1e-2);
+
+    let mut s = String::new();
+    poloto::frame_build()
+        .data(plots!(
+            plot("ecdf").line(e.points()),
+            plot("kde").line(points)
+        ))
+        .build_and_label(("title", "x", "y"))
+        .headless()
+        .render_fmt_write(&mut s)
+        .unwrap();
+    assert!(s.contains("poloto_line poloto1"));
+}
+
+#[test]
+fn distribution_skips_infinite() {
+    use poloto::build::distribution::{Ecdf, Kde};
+
+    let samples = [1.0, 2.0, f64::INFINITY, f64::NEG_INFINITY];
+
+    let e = Ecdf::new(samples);
+    assert_eq!(e.eval(1.0), 0.5);
+    assert_eq!(e.points().last(), Some(&(2.0, 1.0)));
+
+    let points = Kde::new(samples).points();
+    assert!(points.iter().all(|(x, y)| x.is_finite() && y.is_finite()));
+}
@@ -0,0 +1,47 @@
+use poloto::build::plot;
+
+#[test]
+fn downsample() {
+    use poloto::render::downsample::Downsample;
+
+    //A long series with a single spike and a hole.
+    let data = (0..20_000).map(|i| {
+        let x = i as f64;
+        let y = match i {
+            12_345 => 10.0,
+            15_000 => f64::NAN,
+            _ => (x / 500.0).sin(),
+        };
+        (x, y)
+    });
+
+    let render = |mode: Option<Downsample>| {
+        let mut frame = poloto::frame();
+        if let Some(mode) = mode {
+            frame.with_downsample(mode);
+        }
+        let mut s = String::new();
+        frame
+            .build()
+            .data(plot("a").line(data.clone()))
+            .build_and_label(("title", "x", "y"))
+            .headless()
+            .render_fmt_write(&mut s)
+            .unwrap();
+        let path = s.split("poloto_line poloto0").nth(1).unwrap();
+        path[..path.find("/>").unwrap()].to_string()
+    };
+
+    let full = render(None);
+    assert_eq!(full.matches(" L ").count(), 19_997);
+
+    for mode in [Downsample::Lttb, Downsample::MinMax] {
+        let path = render(Some(mode));
+        //Bounded by the 500 pixel wide plot area.
+        assert!(path.matches(" L ").count() < 4 * 500, "{:?}", mode);
+        //The spike is kept.
+        assert!(path.contains(" 100.00 "), "{:?}", mode);
+        //The hole still splits the line.
+        assert_eq!(path.matches("M ").count(), 2, "{:?}", mode);
+    }
+}
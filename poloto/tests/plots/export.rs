@@ -0,0 +1,187 @@
+use super::*;
+use poloto::build::plot;
+use poloto::render::export::DataFmt;
+
+#[test]
+fn export_csv_json() {
+    let l1 = plot("a,b").line([[0.0, 1.0], [1.0, f64::NAN], [2.0, 3.5]]);
+    let l2 = plot("c").scatter([[1.0, 2.0]]);
+
+    let data = poloto::frame_build()
+        .data(plots!(l1, l2))
+        .build_and_label(("title", "x", "y"));
+
+    let d = data.plot_data(DataFmt::Raw);
+
+    assert_eq!(
+        d.to_csv(),
+        "name,type,x,y\n\"a,b\",line,0.0,1.0\n\"a,b\",line,1.0,\n\"a,b\",line,2.0,3.5\nc,scatter,1.0,2.0\n"
+    );
+    assert_eq!(
+        d.to_json(),
+        r#"{"series":[{"name":"a,b","type":"line","points":[[0.0,1.0],[1.0,null],[2.0,3.5]]},{"name":"c","type":"scatter","points":[[1.0,2.0]]}]}"#
+    );
+}
+
+#[test]
+fn export_metadata() {
+    let l1 = plot("cos").line([[0i128, 1], [1, 2]]);
+
+    let data = poloto::frame_build()
+        .data(l1)
+        .build_and_label(("title", "x", "y"));
+
+    let s = data
+        .with_metadata(DataFmt::Ticks)
+        .headless()
+        .render_string()
+        .unwrap();
+
+    assert!(s.contains(r#"{"series":[{"name":"cos","type":"line","points":[[0,1],[1,2]]}]}"#));
+}
+
+#[test]
+fn export_full_precision() {
+    let render = || {
+        poloto::frame_build()
+            .data(plot("a").line([[1000.0001234, 1.0], [1000.0003, 2.0]]))
+            .build_and_label(("title", "x", "y"))
+    };
+
+    let raw = render().plot_data(DataFmt::Raw);
+    assert_eq!(raw.wheres(), [None, None]);
+    assert!(raw.to_csv().contains("a,line,1000.0001234,1.0\n"));
+
+    //Ticks relative to an offset are exported along with the offset.
+    let ticks = render().plot_data(DataFmt::Ticks);
+    let [xwher, ywher] = ticks.wheres();
+    let xwher = xwher.unwrap();
+    assert!(xwher.starts_with("where j="));
+    assert_eq!(ywher, None);
+    assert!(ticks
+        .to_csv()
+        .starts_with(&format!("name,type,x {},y\n", xwher)));
+    assert!(ticks
+        .to_json()
+        .starts_with(&format!(r#"{{"x_where":"{}","series":"#, xwher)));
+}
+
+#[test]
+fn export_skips_refline() {
+    use poloto::build::{hline, vspan};
+
+    let render = || {
+        poloto::frame_build()
+            .data(plots!(
+                vspan(1.0, 2.0),
+                plot("a").line([[0.0, 1.0], [3.0, 2.0]]),
+                hline(1.5).with_label("SLO")
+            ))
+            .build_and_label(("title", "x", "y"))
+    };
+
+    assert_eq!(
+        render().plot_data(DataFmt::Raw).to_csv(),
+        "name,type,x,y\na,line,0.0,1.0\na,line,3.0,2.0\n"
+    );
+
+    let s = render()
+        .with_metadata(DataFmt::Raw)
+        .headless()
+        .render_string()
+        .unwrap();
+    let start = s.find("<metadata").unwrap();
+    let end = s.find("</metadata>").unwrap();
+    let metadata = &s[start..end];
+    assert!(metadata.contains(r#""name":"a""#));
+    assert!(!metadata.contains("refline"));
+    assert!(!metadata.contains("SLO"));
+}
+
+#[test]
+fn export_vega_lite() {
+    let l1 = plot("a").histogram([[0i128, 1], [1, 2], [2, 0]]);
+    let l2 = plot("").text();
+
+    let data = poloto::frame_build()
+        .data(plots!(l1, l2, poloto::build::markers([], [0])))
+        .map_xticks(|_| poloto::ticks::from_iter([0, 1, 2]))
+        .build_and_label(("title", "x", "y"));
+
+    let s = data.vega_lite().unwrap();
+
+    assert!(s.starts_with(
+        r#"{"$schema":"https://vega.github.io/schema/vega-lite/v5.json","title":"title","#
+    ));
+    assert!(s.contains(r#""data":{"values":[{"x":0,"y":1,"x2":1},{"x":1,"y":2,"x2":2}]}"#));
+    assert!(s.contains(
+        r#""x":{"field":"x","type":"quantitative","title":"x","scale":{"domain":[0,2]},"axis":{"values":[0,1,2]}}"#
+    ));
+    assert_eq!(s.matches("\"mark\"").count(), 1);
+}
+
+#[test]
+fn export_vega_lite_candles_and_arrows() {
+    use poloto::build::candle::Candle;
+    use poloto::build::quiver::Quiver;
+
+    let s = poloto::frame_build()
+        .data(plot("c").candlestick([Candle::new(1.0, 2.0, 4.0, 1.0, 3.0)]))
+        .build_and_label(("title", "x", "y"))
+        .vega_lite()
+        .unwrap();
+
+    assert!(s.contains(r#"{"x":1.0,"open":2.0,"high":4.0,"low":1.0,"close":3.0}"#));
+
+    let s = poloto::frame_build()
+        .data(plot("q").quiver(Quiver::new([(0.0, 0.0, 1.0, 0.0)])))
+        .build_and_label(("title", "x", "y"))
+        .vega_lite()
+        .unwrap();
+
+    assert!(s.contains(r#""mark":{"type":"point","shape":"arrow","filled":true}"#));
+    assert!(s.contains(r#"{"x":0.0,"y":0.0,"dx":1.0,"dy":0.0,"angle":90.0,"magnitude":1.0}"#));
+}
+
+#[test]
+fn accessible_svg() {
+    let l1 = plot("a").line([[0i128, 1], [2, 3]]);
+    let l2 = plot("b").scatter([[1i128, 2]]);
+
+    let data = poloto::frame_build()
+        .data(plots!(l1, l2))
+        .build_and_label(("my title", "time", "speed"));
+
+    let table = data.plot_data(DataFmt::Raw);
+
+    let s = data
+        .append_to(poloto::header().light_theme())
+        .render_string()
+        .unwrap();
+
+    assert!(s.contains(r#"role="img""#));
+    assert!(s.contains("my title</title>"));
+    assert!(s.contains("x axis time from 0 to 2. y axis speed from 1 to 3. Plots: a, b.</desc>"));
+
+    let mut t = String::new();
+    tagu::render(table.html_table(), &mut t).unwrap();
+    assert_eq!(t.matches("<tr>").count(), 4);
+    assert!(t.contains("<td>b</td>"));
+}
+
+#[test]
+fn id_prefix() {
+    let s = poloto::frame()
+        .with_id_prefix("fig1_")
+        .build()
+        .data(plot("a").line([[0i128, 1], [2, 3]]))
+        .build_and_label(("my title", "x", "y"))
+        .append_to(poloto::header())
+        .render_string()
+        .unwrap();
+
+    assert!(s.contains(r#"<title id="fig1_title">"#));
+    assert!(s.contains(r#"<desc id="fig1_desc">"#));
+    assert!(s.contains(r#"id="fig1_plot0""#));
+    assert!(!s.contains("id=\"poloto_"));
+}
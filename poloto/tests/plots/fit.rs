@@ -0,0 +1,51 @@
+#[test]
+fn fit() {
+    use poloto::build::fit::{Fit, Model};
+
+    let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+
+    let f = Fit::new(Model::Linear, [(0.0, 1.0), (1.0, 3.0), (2.0, 5.0)]).unwrap();
+    assert!(close(f.coefficients()[0], 1.0) && close(f.coefficients()[1], 2.0));
+    assert!(close(f.r_squared(), 1.0));
+    assert_eq!(f.to_string(), "y = 2.00x + 1.00 (R² = 1.000)");
+
+    let f = Fit::new(
+        Model::Polynomial(2),
+        (-3..=3).map(|x| (x as f64, (x * x) as f64 - 1.0)),
+    )
+    .unwrap();
+    assert!(close(f.eval(4.0), 15.0));
+
+    //Far from zero x values like timestamps are still fitted accurately.
+    let f = Fit::new(
+        Model::Polynomial(2),
+        (-3..=3).map(|x| (1.6e9 + x as f64, (x * x) as f64 - 1.0)),
+    )
+    .unwrap();
+    assert!((f.eval(1.6e9 + 4.0) - 15.0).abs() < 1e-3);
+    let f = Fit::new(Model::Linear, (0..10).map(|x| (1e6 + x as f64, x as f64))).unwrap();
+    assert!(close(f.coefficients()[1], 1.0));
+
+    let f = Fit::new(
+        Model::Exponential,
+        (0..5).map(|x| (x as f64, 3.0 * (0.5 * x as f64).exp())),
+    )
+    .unwrap();
+    assert!(close(f.coefficients()[0], 3.0) && close(f.coefficients()[1], 0.5));
+
+    let f = Fit::new(
+        Model::Power,
+        (1..5).map(|x| (x as f64, 2.0 * (x as f64).powf(1.5))),
+    )
+    .unwrap();
+    assert!(close(f.coefficients()[1], 1.5));
+
+    //The curve covers the x range of the data.
+    let p = f.points(4);
+    assert_eq!(p.len(), 4);
+    assert_eq!((p[0].0, p[3].0), (1.0, 4.0));
+
+    //Not enough points.
+    assert!(Fit::new(Model::Polynomial(2), [(0.0, 1.0), (1.0, 2.0)]).is_none());
+    assert!(Fit::new(Model::Power, [(-1.0, 1.0), (0.0, 2.0)]).is_none());
+}
@@ -0,0 +1,45 @@
+#[test]
+fn adaptive() {
+    use poloto::build::function::{adaptive, parametric};
+    use std::f64::consts::{PI, TAU};
+
+    //More points where it bends, all of them on the curve.
+    let p = adaptive(|x| (x * x).sin(), [0.0, 6.0]).points();
+    assert!(p.iter().all(|&(x, y)| (y - (x * x).sin()).abs() < 1e-12));
+    let spacing = |a: f64, b: f64| {
+        let xs: Vec<_> = p
+            .iter()
+            .map(|p| p.0)
+            .filter(|x| *x >= a && *x <= b)
+            .collect();
+        (b - a) / xs.len() as f64
+    };
+    assert!(spacing(5.0, 6.0) < spacing(0.0, 1.0) / 4.0);
+
+    //A hole at each jump, and no segment across one.
+    let p = adaptive(f64::floor, [0.0, 2.5]).points();
+    assert_eq!(p.iter().filter(|p| p.1.is_nan()).count(), 2);
+    assert!(p
+        .windows(2)
+        .all(|w| w[0].1.is_nan() || w[1].1.is_nan() || (w[1].1 - w[0].1).abs() < 0.5));
+
+    //Asymptotes become holes and don't blow up the range.
+    let p = adaptive(f64::tan, [-3.0, 3.0]).points();
+    assert!(p.iter().filter(|p| p.1.is_nan()).count() >= 2);
+    assert!(p.iter().all(|p| p.1.is_nan() || p.1.abs() < 100.0));
+    assert!(p.windows(2).all(|w| !(w[0].0 < PI / 2.0
+        && w[1].0 > PI / 2.0
+        && w[1].1.is_finite()
+        && w[0].1.is_finite())));
+
+    let p = adaptive(|x| 1.0 / x, [-1.0, 1.0])
+        .with_y_range([-10.0, 10.0])
+        .points();
+    assert!(p.iter().all(|p| p.1.is_nan() || p.1.abs() <= 10.0));
+
+    //A circle ends where it started.
+    let p = parametric(|t| (t.cos(), t.sin()), [0.0, TAU]).points();
+    assert!(p.iter().all(|(x, y)| (x.hypot(*y) - 1.0).abs() < 1e-12));
+    let (first, last) = (p[0], p[p.len() - 1]);
+    assert!((first.0 - last.0).abs() < 1e-12 && (first.1 - last.1).abs() < 1e-12);
+}
@@ -0,0 +1,24 @@
+use poloto::build::plot;
+
+#[test]
+fn heatmap() {
+    use poloto::build::heatmap::Heatmap;
+
+    let heatmap = Heatmap::new([0.0, 1.0, 2.0], [0.0, 1.0], [[1.0, f64::NAN]]);
+
+    let data = poloto::frame_build()
+        .data(plot("h").heatmap(heatmap))
+        .build_and_label(("title", "x", "y"));
+
+    let csv = data
+        .plot_data(poloto::render::export::DataFmt::Raw)
+        .to_csv();
+    assert!(csv.starts_with("name,type,x,y,value\nh,heatmap,0.0,0.0,\nh,heatmap,1.0,1.0,1.0\n"));
+
+    let mut s = String::new();
+    data.headless().render_fmt_write(&mut s).unwrap();
+
+    assert_eq!(s.matches(r#"class="poloto_cell""#).count(), 1);
+    assert!(s.contains("<title>1</title>"));
+    assert!(s.contains(r#"class="poloto_colorbar""#));
+}
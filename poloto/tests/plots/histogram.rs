@@ -0,0 +1,53 @@
+use poloto::build::plot;
+
+#[test]
+fn histogram_bins() {
+    use poloto::build::histogram::{Bins, Histogram};
+
+    let samples = [0.0, 1.0, 1.0, 2.0, 2.0, 2.0, 3.0, f64::NAN];
+
+    //The largest sample goes in the last bin, and a closing edge is added.
+    let h = Histogram::new(samples, Bins::Count(3));
+    assert_eq!(h.counts(), &[1, 2, 4]);
+    assert_eq!(h.points(), [(0.0, 1.0), (1.0, 2.0), (2.0, 4.0), (3.0, 0.0)]);
+
+    //Infinities don't stretch the bins.
+    let h = Histogram::new(
+        samples
+            .into_iter()
+            .chain([f64::INFINITY, f64::NEG_INFINITY]),
+        Bins::Count(3),
+    );
+    assert_eq!(h.edges(), [0.0, 1.0, 2.0, 3.0]);
+    assert_eq!(h.counts(), &[1, 2, 4]);
+
+    assert_eq!(Histogram::new(samples, Bins::Sturges).counts().len(), 4);
+    assert_eq!(
+        Histogram::new([5.0; 4], Bins::FreedmanDiaconis)
+            .edges()
+            .len(),
+        4
+    );
+
+    let h = Histogram::new(samples, Bins::Width(0.5)).density();
+    let area: f64 = h.heights().iter().map(|h| h * 0.5).sum();
+    assert!((area - 1.0).abs() < 1e-9);
+
+    let h = Histogram::new(samples, Bins::Edges(vec![0.5, 1.5, 2.5])).probability();
+    assert_eq!(h.heights(), [2.0 / 5.0, 3.0 / 5.0]);
+
+    //Integer bins have integer edges and each bin includes its left edge only.
+    let ints = [0i128, 1, 1, 2, 9];
+    assert_eq!(Histogram::new(ints, Bins::Width(5)).counts(), &[4, 1]);
+    assert_eq!(Histogram::new(ints, Bins::Count(3)).edges(), &[0, 4, 8, 12]);
+
+    let mut s = String::new();
+    poloto::frame_build()
+        .data(plot("a").histogram(Histogram::new(samples, Bins::Count(3)).points()))
+        .build_and_label(("title", "x", "y"))
+        .headless()
+        .render_fmt_write(&mut s)
+        .unwrap();
+    //Three bars and the legend.
+    assert_eq!(s.matches("<rect x=").count(), 4);
+}
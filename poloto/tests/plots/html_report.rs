@@ -0,0 +1,55 @@
+use super::*;
+use poloto::build::plot;
+
+#[test]
+fn html_report() -> fmt::Result {
+    let mut report = poloto::html::Report::new("Report & co");
+
+    for i in 0..2 {
+        let data = poloto::frame_build()
+            .data(plot("a").line([[0, i], [1, 1]]))
+            .build_and_label(("title", "x", "y"));
+
+        report.add_figure(format!("Figure {}", i), "caption", data)?;
+    }
+
+    let s = report.render_string()?;
+
+    assert_eq!(s.matches("<style>").count(), 1);
+    assert_eq!(s.matches(".poloto_background{fill:AliceBlue;}").count(), 1);
+    assert!(s.contains("@media (prefers-color-scheme: dark)"));
+    assert!(s.contains("<h1>Report &amp; co</h1>"));
+    assert!(s.contains(r#"<title id="poloto_fig1_title">"#));
+    assert!(s.contains(r#"<desc id="poloto_fig0_desc">"#));
+    assert!(s.contains(r#"id="poloto_fig1_plot0""#));
+    assert!(!s.contains(r#"id="poloto_title""#));
+
+    let mut w = util::create_test_file("html_report.html");
+    write!(w, "{}", s)
+}
+
+#[test]
+fn html_report_theme() -> fmt::Result {
+    let mut report = poloto::html::Report::new("Report");
+    report
+        .with_theme(poloto::render::Theme::auto_css_vars())
+        .add_style(".poloto_line{stroke-dasharray:4}");
+
+    let data = poloto::frame_build()
+        .data(plot("a").line([[0, 0], [1, 1]]))
+        .build_and_label(("title", "x", "y"));
+    report.add_figure("Line", "", data)?;
+
+    let pie = poloto::frame_build()
+        .pie(poloto::build::pie::Pie::new([("a", 1.0), ("b", 2.0)]))
+        .label(("pie", "", ""));
+    report.add_figure("Pie", "", pie)?;
+
+    let s = report.render_string()?;
+
+    assert_eq!(s.matches("<style>").count(), 1);
+    assert!(s.contains("var(--poloto-bg,AliceBlue)"));
+    assert!(s.contains(".poloto_line{stroke-dasharray:4}"));
+    assert!(s.contains(r#"<title id="poloto_fig1_title">"#));
+    Ok(())
+}
@@ -1,5 +1,29 @@
+mod annotate;
+mod bubble;
+mod candle;
+mod contour;
+mod distribution;
+mod downsample;
+mod export;
+mod fit;
+mod function;
+mod heatmap;
+mod histogram;
 mod html_plots;
+#[cfg(feature = "html")]
+mod html_report;
+mod marker;
+mod pie;
+mod polar;
+mod quiver;
+mod refline;
+mod rolling;
 mod simple;
+mod simplify;
+mod smooth;
+mod step;
+mod streamed;
+mod theme;
 mod util;
 
 use fmt::Write;
@@ -0,0 +1,28 @@
+use super::*;
+use poloto::build::plot;
+
+#[test]
+fn markers() {
+    use poloto::build::Marker;
+
+    let l1 =
+        plot("a")
+            .with_marker(Marker::Square)
+            .scatter([[0.0, 0.0], [1.0, f64::NAN], [2.0, 2.0]]);
+    let l2 = plot("b")
+        .with_marker(Marker::Plus)
+        .line([[0.0, 1.0], [2.0, 1.0]]);
+
+    let mut s = String::new();
+    poloto::frame_build()
+        .data(plots!(l1, l2))
+        .build_and_label(("title", "x", "y"))
+        .headless()
+        .render_fmt_write(&mut s)
+        .unwrap();
+
+    //Two squares in the plot and one in the legend.
+    assert_eq!(s.matches("h 10.00 v 10.00 h -10.00 Z").count(), 3);
+    assert!(s.contains("poloto_legend poloto_text poloto_line poloto_marker poloto1"));
+    assert_eq!(s.matches("m -5.00 -5.00 v 10.00").count(), 3);
+}
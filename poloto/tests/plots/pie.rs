@@ -0,0 +1,38 @@
+#[test]
+fn pie() {
+    use poloto::build::pie::Pie;
+
+    let render = |pie: Pie<&str>| {
+        let mut s = String::new();
+        poloto::frame_build()
+            .pie(pie)
+            .label(("title", "", ""))
+            .headless()
+            .render_fmt_write(&mut s)
+            .unwrap();
+        s
+    };
+
+    let s = render(Pie::new([("a", 1.0), ("b", 3.0), ("c", 0.0)]).with_percentages());
+    assert_eq!(s.matches("<path ").count(), 2);
+    assert!(s.contains("<title>b: 3 (75.0%)</title>"));
+    assert!(s.contains(">25.0%</tspan>"));
+    assert!(s.contains("Slices: a 25.0%, b 75.0%, c 0.0%."));
+    //No axes are drawn.
+    assert!(!s.contains("poloto_ticks"));
+    //The first slice starts at the top and goes clockwise.
+    assert!(s.contains(r#"d="M 400.00 100.00 A 150.00 150.00 0 0 1 506.07 143.93 "#));
+
+    let s = render(Pie::new([("a", 1.0)]).donut(0.5));
+    assert!(s.contains("L 400.00 175.00 A 75.00 75.00 0 0 0 "));
+
+    //The legend text wraps around the css classes like the slices do.
+    let names = ["a", "b", "c", "d", "e", "f", "g", "h", "i"];
+    let s = render(Pie::new(names.map(|n| (n, 1.0))));
+    assert!(!s.contains("poloto8"));
+    assert_eq!(
+        s.matches(r#"class="poloto_legend poloto_text poloto_pie poloto0""#)
+            .count(),
+        2
+    );
+}
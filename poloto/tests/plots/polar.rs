@@ -0,0 +1,84 @@
+use poloto::build::plot;
+
+#[test]
+fn polar() {
+    use poloto::build::polar::Radar;
+
+    let mut s = String::new();
+    poloto::frame_build()
+        .polar(plot("a").line([(0.0, 1.0), (std::f64::consts::FRAC_PI_2, 2.0)]))
+        .label(("title", "", ""))
+        .headless()
+        .render_fmt_write(&mut s)
+        .unwrap();
+
+    assert!(s.contains(r#"d=" M 475.00 250.00 L 400.00 100.00""#));
+    assert!(s.contains(">90°</tspan>"));
+    assert!(!s.contains("poloto_ticks poloto_x"));
+
+    let radar = Radar::new(["a", "b", "c", "d"]);
+    let mut s = String::new();
+    poloto::frame_build()
+        .polar(plot("x").line_fill(radar.points([4.0, 4.0, 2.0, 4.0])))
+        .with_radar(&radar)
+        .label(("title", "", ""))
+        .headless()
+        .render_fmt_write(&mut s)
+        .unwrap();
+
+    //Clockwise from the top and closed on itself.
+    assert!(s.contains(
+        r#"d=" M 400.00 100.00 L 550.00 250.00 L 400.00 325.00 L 250.00 250.00 L 400.00 100.00 Z""#
+    ));
+    assert!(s.contains(r#"<tspan x="575.00" y="250.00">b</tspan>"#));
+}
+
+#[test]
+fn polar_smooth_and_degrees() {
+    use poloto::build::polar::Radar;
+
+    let radar = Radar::new(["a", "b", "c", "d"]).with_degrees();
+    let render = |smooth: bool| {
+        let points = radar.points([4.0, 4.0, 2.0, 4.0]);
+        let p = if smooth {
+            plot("x").smooth().line_fill(points)
+        } else {
+            plot("x").line_fill(points)
+        };
+        let mut s = String::new();
+        poloto::frame_build()
+            .polar(p)
+            .with_degrees()
+            .with_radar(&radar)
+            .label(("title", "", ""))
+            .headless()
+            .render_fmt_write(&mut s)
+            .unwrap();
+        s
+    };
+
+    //The radar keeps the frame in degrees.
+    let s = render(false);
+    assert!(s.contains(
+        r#"d=" M 400.00 100.00 L 550.00 250.00 L 400.00 325.00 L 250.00 250.00 L 400.00 100.00 Z""#
+    ));
+
+    //The smooth fill goes through the same points, with more segments in between.
+    let s = render(true);
+    assert!(s.contains(r#"d=" M 400.00 100.00 L"#));
+    assert!(s.contains(" L 550.00 250.00 L"));
+    assert_eq!(
+        s.matches(" L ").count() - render(false).matches(" L ").count(),
+        4 * 15
+    );
+}
+
+#[test]
+#[should_panic(expected = "only line, scatter and fill plots can be drawn on polar axes")]
+fn polar_rejects_histogram() {
+    let _ = poloto::frame_build()
+        .polar(plot("a").histogram([(0.0, 1.0), (1.0, 2.0)]))
+        .label(("title", "", ""))
+        .headless()
+        .render_string();
+}
@@ -0,0 +1,82 @@
+use super::*;
+use poloto::build::plot;
+
+#[test]
+fn quiver() {
+    use poloto::build::quiver::Quiver;
+
+    let field = [
+        (0.0, 0.0, 1.0, 0.0),
+        (1.0, 0.0, 0.0, 2.0),
+        (0.0, 1.0, f64::NAN, 1.0),
+    ];
+
+    let mut s = String::new();
+    poloto::frame_build()
+        .data(
+            plot("a")
+                .quiver(Quiver::new(field).with_colormap(poloto::colormap::Colormap::viridis())),
+        )
+        .build_and_label(("title", "x", "y"))
+        .headless()
+        .render_fmt_write(&mut s)
+        .unwrap();
+
+    //Each color gets its own marker so that the arrowheads don't rely on context-stroke.
+    assert!(!s.contains("context-stroke"));
+    assert!(s.contains(r#"<marker id="poloto_arrow0_0""#));
+    assert!(s.contains(r#"fill="rgb(68,1,84)" stroke="none"/>"#));
+    //The longest arrow is as long as the spacing between points.
+    assert!(s.contains(r#"<line x1="150.00" y1="250.00" x2="400.00" y2="250.00" marker-end="url(#poloto_arrow0_0)" stroke="rgb(68,1,84)">"#));
+    assert!(s.contains(r#"<line x1="650.00" y1="250.00" x2="650.00" y2="-50.00" marker-end="url(#poloto_arrow0_1)""#));
+    assert!(s.contains(r#"marker-end="url(#poloto_arrow0_legend)""#));
+    assert!(s.contains("\t\t<title>1</title>"));
+    assert!(s.contains("poloto_colorbar"));
+
+    //Marker ids stay unique when the css classes wrap around.
+    let mut s = String::new();
+    poloto::frame()
+        .num_css_class(Some(1))
+        .build()
+        .data(plots!(
+            plot("a").quiver(Quiver::new(field)),
+            plot("b").quiver(Quiver::new(field))
+        ))
+        .build_and_label(("title", "x", "y"))
+        .headless()
+        .render_fmt_write(&mut s)
+        .unwrap();
+
+    assert!(s.contains(r#"<marker id="poloto_arrow0""#));
+    assert!(s.contains(r#"<marker id="poloto_arrow1""#));
+    assert_eq!(
+        s.matches(r#"class="poloto0 poloto_fill" stroke="none"/>"#)
+            .count(),
+        2
+    );
+    assert_eq!(s.matches(r#"marker-end="url(#poloto_arrow1)""#).count(), 3);
+}
+
+#[test]
+fn quiver_scale_per_axis() {
+    use poloto::build::quiver::Quiver;
+    use poloto::render::export::DataFmt;
+
+    //The axes have very different units, but the arrows are sized relative to the plot area.
+    let field =
+        (0..3).flat_map(|i| (0..3).map(move |j| (i as f64 * 100.0, j as f64 * 0.1, 1.0, 1.0)));
+
+    let data = poloto::frame_build()
+        .data(plot("a").quiver(Quiver::new(field)))
+        .build_and_label(("title", "x", "y"));
+
+    assert!(data
+        .plot_data(DataFmt::Raw)
+        .to_csv()
+        .starts_with("name,type,x,y,value,dx,dy\na,quiver,0.0,0.0,1.4142135623730951,1.0,1.0\n"));
+
+    let s = data.headless().render_string().unwrap();
+    assert!(s.contains(
+        r#"<line x1="150.00" y1="400.00" x2="326.78" y2="293.93" marker-end="url(#poloto_arrow0)">"#
+    ));
+}
@@ -0,0 +1,33 @@
+use super::*;
+use poloto::build::plot;
+
+#[test]
+fn refline() {
+    use poloto::build::{hline, hspan, vline, vspan};
+
+    let mut s = String::new();
+    poloto::frame_build()
+        .data(plots!(
+            vspan(1.0, 2.0).with_label("incident"),
+            plot("a").line([(0.0, 0.0), (4.0, 2.0)]),
+            hline(1.0).with_label("SLO"),
+            vline(3.0),
+            hspan(10.0, 20.0).grow_area(false)
+        ))
+        .build_and_label(("title", "x", "y"))
+        .headless()
+        .render_fmt_write(&mut s)
+        .unwrap();
+
+    //Reference lines go all the way across the plot area.
+    assert!(s.contains(r#"<line class="poloto_imgs poloto_refline poloto_hline" x1="150.00" y1="250.00" x2="650.00" y2="250.00"/>"#));
+    assert!(s.contains(r#"<line class="poloto_imgs poloto_refline poloto_vline" x1="525.00" y1="100.00" x2="525.00" y2="400.00"/>"#));
+    assert!(s.contains(r#"<rect class="poloto_imgs poloto_refline poloto_vspan" x="275.00" y="100.00" width="125.00" height="300.00"/>"#));
+    assert!(s.contains(r#"text-anchor="end" dominant-baseline="auto">SLO</text>"#));
+    assert!(s.contains(">incident</text>"));
+    //They don't get a legend entry or use up a color.
+    assert!(s.contains("poloto_line poloto0 poloto_stroke"));
+    //The span that didn't grow the area is outside of it.
+    assert!(!s.contains("poloto_hspan"));
+    assert!(!s.contains("poloto_legend poloto_text poloto1"));
+}
@@ -0,0 +1,54 @@
+#[test]
+fn rolling() {
+    use poloto::build::rolling::Aggregate;
+    use poloto::prelude::{Resamplable, Rolling};
+
+    let data = [
+        (0.0, 4.0),
+        (1.0, 2.0),
+        (2.0, f64::NAN),
+        (3.0, 6.0),
+        (4.0, 0.0),
+    ];
+    let strip = |v: Vec<(f64, f64)>| -> Vec<_> { v.into_iter().map(|(_, y)| y).collect() };
+    let same = |a: Vec<f64>, b: &[f64]| {
+        a.len() == b.len()
+            && a.iter()
+                .zip(b)
+                .all(|(a, b)| a == b || (a.is_nan() && b.is_nan()))
+    };
+
+    //The hole is passed through and skipped by the window.
+    let nan = f64::NAN;
+    assert!(same(
+        strip(data.into_iter().moving_average(2).collect()),
+        &[4.0, 3.0, nan, 4.0, 3.0]
+    ));
+    assert!(same(
+        strip(data.into_iter().exponential_moving_average(0.5).collect()),
+        &[4.0, 3.0, nan, 4.5, 2.25]
+    ));
+    assert!(same(
+        strip(data.into_iter().rolling_min(3).collect()),
+        &[4.0, 2.0, nan, 2.0, 0.0]
+    ));
+    assert!(same(
+        strip(data.into_iter().rolling_max(3).collect()),
+        &[4.0, 4.0, nan, 6.0, 6.0]
+    ));
+    assert!(same(
+        strip(data.into_iter().rolling_percentile(3, 50.0).collect()),
+        &[4.0, 3.0, nan, 4.0, 2.0]
+    ));
+
+    //Empty slots become a hole.
+    let data = [(0.1, 1.0), (0.6, 3.0), (1.2, 5.0), (3.5, 7.0), (3.9, 9.0)];
+    let r: Vec<_> = data.into_iter().resample(1.0, Aggregate::Mean).collect();
+    assert_eq!(r.len(), 4);
+    assert_eq!(r[0], (0.0, 2.0));
+    assert_eq!(r[1], (1.0, 5.0));
+    assert!(r[2].0 == 2.0 && r[2].1.is_nan());
+    assert_eq!(r[3], (3.0, 8.0));
+    let r: Vec<_> = data.into_iter().resample(1.0, Aggregate::Last).collect();
+    assert_eq!(r[3], (3.0, 9.0));
+}
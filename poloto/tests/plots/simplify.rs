@@ -0,0 +1,50 @@
+use super::*;
+use poloto::build::plot;
+
+#[test]
+fn simplify() {
+    //A straight run, a repeated point, a hole, and a fill.
+    let data = [
+        (0.0, 0.0),
+        (1.0, 1.0),
+        (2.0, 2.0),
+        (3.0, 3.0),
+        (3.0, 3.0000001),
+        (4.0, 0.0),
+        (5.0, f64::NAN),
+        (6.0, 1.0),
+        (7.0, 2.0),
+    ];
+
+    let render = |simplify: bool| {
+        let mut frame = poloto::frame();
+        if simplify {
+            frame.with_simplify(0.5);
+        }
+        let mut s = String::new();
+        frame
+            .build()
+            .data(plots!(
+                plot("a").line(data),
+                plot("b").line_fill(data.iter().map(|&(x, y)| (x, y / 2.0)))
+            ))
+            .build_and_label(("title", "x", "y"))
+            .headless()
+            .render_fmt_write(&mut s)
+            .unwrap();
+        s
+    };
+
+    let full = render(false);
+    assert!(full.contains(r#"d=" M 150.00 400.00 L 221.43 300.00 L 292.86 200.00 L 364.29 100.00 L 364.29 100.00 L 435.71 400.00 M 578.57 300.00 L 650.00 200.00""#));
+
+    //The straight run and the repeated point are gone and the hole is kept.
+    let s = render(true);
+    assert!(s.contains(
+        r#"d=" M 150.00 400.00 L 364.29 100.00 l 71.42 300.00 M 578.57 300.00 L 650.00 200.00""#
+    ));
+    assert!(s.contains(
+        r#"d=" M 150.00 400.00 L 364.29 250.00 l 71.42 150.00 M 578.57 350.00 l 71.43 -50.00 l 0.00 100.00 Z""#
+    ));
+    assert!(s.len() < full.len());
+}
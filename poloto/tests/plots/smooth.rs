@@ -0,0 +1,33 @@
+use super::*;
+use poloto::build::plot;
+
+#[test]
+fn smooth() {
+    let data = [
+        [0.0, 0.0],
+        [1.0, 1.0],
+        [2.0, 1.0],
+        [3.0, f64::NAN],
+        [4.0, 0.0],
+        [5.0, 2.0],
+    ];
+
+    let mut s = String::new();
+    poloto::frame_build()
+        .data(plots!(
+            plot("a").smooth().line(data),
+            plot("b").smooth().line_fill(data)
+        ))
+        .build_and_label(("title", "x", "y"))
+        .headless()
+        .render_fmt_write(&mut s)
+        .unwrap();
+
+    //The flat segment stays flat, so there is no overshoot.
+    assert!(s.contains(
+        "C 183.33 350.00, 216.67 250.00, 250.00 250.00 C 283.33 250.00, 316.67 250.00, 350.00 250.00 "
+    ));
+    //Holes split the curve.
+    assert_eq!(s.matches("M 550.00 400.00 C").count(), 1);
+    assert!(s.contains("L 350.00 400.00 Z M 550.00 400.00"));
+}
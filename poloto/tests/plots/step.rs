@@ -0,0 +1,32 @@
+use poloto::build::plot;
+
+#[test]
+fn step() {
+    use poloto::build::Step;
+
+    let render = |step| {
+        let mut s = String::new();
+        poloto::frame_build()
+            .data(plot("s").step(
+                step,
+                [
+                    [0.0, 0.0],
+                    [1.0, 1.0],
+                    [2.0, f64::NAN],
+                    [3.0, 0.0],
+                    [4.0, 1.0],
+                ],
+            ))
+            .build_and_label(("title", "x", "y"))
+            .headless()
+            .render_fmt_write(&mut s)
+            .unwrap();
+        s
+    };
+
+    assert!(render(Step::Post)
+        .contains(r#"d=" M 150.00 400.00 H 275.00 V 100.00 M 525.00 400.00 H 650.00 V 100.00""#));
+    assert!(render(Step::Pre)
+        .contains(r#"d=" M 150.00 400.00 V 100.00 H 275.00 M 525.00 400.00 V 100.00 H 650.00""#));
+    assert!(render(Step::Mid).contains(r#"d=" M 150.00 400.00 H 212.50 V 100.00 H 275.00 "#));
+}
@@ -0,0 +1,52 @@
+use poloto::build;
+use poloto::build::plot;
+
+#[test]
+fn streamed() {
+    use std::cell::Cell;
+
+    let count = Cell::new(0);
+    let source = || {
+        (0..1000).map(|i| {
+            count.set(count.get() + 1);
+            (i as f64, (i % 7) as f64)
+        })
+    };
+
+    //The bounds come from a first pass, and the points from a second one.
+    let frame = poloto::frame_build().data(plot("a").line(build::streamed(source)));
+    assert_eq!(count.get(), 1000);
+    let mut streamed = String::new();
+    frame
+        .build_and_label(("title", "x", "y"))
+        .headless()
+        .render_fmt_write(&mut streamed)
+        .unwrap();
+    assert_eq!(count.get(), 2000);
+
+    //Nothing is iterated until rendering when the bounds are given.
+    count.set(0);
+    let frame = poloto::frame_build().data(plot("a").line(build::bounded(
+        source(),
+        [0.0, 999.0],
+        [0.0, 6.0],
+    )));
+    assert_eq!(count.get(), 0);
+    let mut bounded = String::new();
+    frame
+        .build_and_label(("title", "x", "y"))
+        .headless()
+        .render_fmt_write(&mut bounded)
+        .unwrap();
+    assert_eq!(count.get(), 1000);
+
+    let mut collected = String::new();
+    poloto::frame_build()
+        .data(plot("a").line(source()))
+        .build_and_label(("title", "x", "y"))
+        .headless()
+        .render_fmt_write(&mut collected)
+        .unwrap();
+    assert_eq!(streamed, collected);
+    assert_eq!(bounded, collected);
+}
@@ -0,0 +1,17 @@
+use poloto::build::plot;
+
+#[test]
+fn auto_theme() {
+    let mut s = String::new();
+    poloto::frame_build()
+        .data(plot("a").line([[0, 0], [1, 1]]))
+        .build_and_label(("title", "x", "y"))
+        .append_to(poloto::header().auto_theme())
+        .render_fmt_write(&mut s)
+        .unwrap();
+
+    assert!(s.contains("@media (prefers-color-scheme: dark)"));
+
+    let vars = poloto::render::Theme::auto_css_vars();
+    assert!(vars.get_str().contains("var(--poloto-color-0,blue)"));
+}